@@ -1,7 +1,7 @@
-use wgpu_launchpad::{Scene, launch, WindowEvent};
+use wgpu_launchpad::{launch, simple_pipeline, Context, PipelineConfig, Scene};
 
 fn main() {
-    launch::<Triangle>();
+    launch::<Triangle>(());
 }
 
 pub struct Triangle {
@@ -9,8 +9,10 @@ pub struct Triangle {
 }
 
 impl Scene for Triangle {
-    fn new(device: &wgpu::Device) -> Triangle {
-        let pipeline = build_pipeline(device);
+    type Args = ();
+
+    fn new(ctx: &mut Context, device: &wgpu::Device, _args: ()) -> Triangle {
+        let pipeline = build_pipeline(ctx, device);
         Triangle { pipeline }
     }
 
@@ -35,48 +37,14 @@ impl Scene for Triangle {
     }
 }
 
-fn build_pipeline(device: &wgpu::Device) -> wgpu::RenderPipeline {
+fn build_pipeline(ctx: &Context, device: &wgpu::Device) -> wgpu::RenderPipeline {
     let vs_module = device.create_shader_module(wgpu::include_spirv!("shaders/shader.vert.spv"));
     let fs_module = device.create_shader_module(wgpu::include_spirv!("shaders/shader.frag.spv"));
 
-    let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
-        label: None,
-        push_constant_ranges: &[],
-        bind_group_layouts: &[],
-    });
-
-    let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
-        label: None,
-        layout: Some(&pipeline_layout),
-        vertex_stage: wgpu::ProgrammableStageDescriptor {
-            module: &vs_module,
-            entry_point: "main",
-        },
-        fragment_stage: Some(wgpu::ProgrammableStageDescriptor {
-            module: &fs_module,
-            entry_point: "main",
-        }),
-        rasterization_state: Some(wgpu::RasterizationStateDescriptor {
-            front_face: wgpu::FrontFace::Ccw,
-            cull_mode: wgpu::CullMode::None,
-            ..Default::default()
-        }),
-        primitive_topology: wgpu::PrimitiveTopology::TriangleList,
-        color_states: &[wgpu::ColorStateDescriptor {
-            format: wgpu::TextureFormat::Bgra8UnormSrgb,
-            color_blend: wgpu::BlendDescriptor::REPLACE,
-            alpha_blend: wgpu::BlendDescriptor::REPLACE,
-            write_mask: wgpu::ColorWrite::ALL,
-        }],
-        depth_stencil_state: None,
-        vertex_state: wgpu::VertexStateDescriptor {
-            index_format: wgpu::IndexFormat::Uint16,
-            vertex_buffers: &[],
-        },
-        sample_count: 1,
-        sample_mask: !0,
-        alpha_to_coverage_enabled: false,
-    });
-
-    pipeline
+    // Drawn in our own pass, which has no depth attachment
+    let config = PipelineConfig {
+        depth_compare: None,
+        ..PipelineConfig::new(ctx, &vs_module, &fs_module)
+    };
+    simple_pipeline(device, config)
 }
@@ -0,0 +1,76 @@
+use std::num::NonZeroU32;
+
+/// Builds a `BindGroupLayout` together with a `BindGroup` matching it, so the two can't
+/// disagree on binding indices or resource types
+#[derive(Default)]
+pub struct BindGroupBuilder<'a> {
+    layout_entries: Vec<wgpu::BindGroupLayoutEntry>,
+    entries: Vec<wgpu::BindGroupEntry<'a>>,
+}
+
+impl<'a> BindGroupBuilder<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Bind `resource` at `binding`, visible to the given stages as `ty`. Panics if the index
+    /// is already taken or the resource doesn't match the binding type.
+    pub fn entry(
+        mut self,
+        binding: u32,
+        visibility: wgpu::ShaderStage,
+        ty: wgpu::BindingType,
+        resource: wgpu::BindingResource<'a>,
+    ) -> Self {
+        assert!(
+            self.entries.iter().all(|entry| entry.binding != binding),
+            "Binding {} is bound twice",
+            binding
+        );
+        let count = match (&ty, &resource) {
+            (wgpu::BindingType::UniformBuffer { .. }, wgpu::BindingResource::Buffer(_))
+            | (wgpu::BindingType::StorageBuffer { .. }, wgpu::BindingResource::Buffer(_))
+            | (wgpu::BindingType::Sampler { .. }, wgpu::BindingResource::Sampler(_))
+            | (wgpu::BindingType::SampledTexture { .. }, wgpu::BindingResource::TextureView(_))
+            | (wgpu::BindingType::StorageTexture { .. }, wgpu::BindingResource::TextureView(_)) => {
+                None
+            }
+            (
+                wgpu::BindingType::SampledTexture { .. },
+                wgpu::BindingResource::TextureViewArray(views),
+            ) => Some(NonZeroU32::new(views.len() as u32).expect("Empty texture view array")),
+            _ => panic!(
+                "Binding {} is declared as {:?} but given another kind of resource",
+                binding, ty
+            ),
+        };
+
+        self.layout_entries.push(wgpu::BindGroupLayoutEntry {
+            binding,
+            visibility,
+            ty,
+            count,
+        });
+        self.entries
+            .push(wgpu::BindGroupEntry { binding, resource });
+        self
+    }
+
+    /// Create the layout and a bind group of it holding every entry's resource
+    pub fn build(
+        self,
+        device: &wgpu::Device,
+        label: Option<&str>,
+    ) -> (wgpu::BindGroupLayout, wgpu::BindGroup) {
+        let layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label,
+            entries: &self.layout_entries,
+        });
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label,
+            layout: &layout,
+            entries: &self.entries,
+        });
+        (layout, bind_group)
+    }
+}
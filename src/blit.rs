@@ -0,0 +1,198 @@
+use crate::{sampler, SamplerConfig, ToneMapping};
+
+/// Layout of the bind group `launch` passes to `Scene::present`: the intermediate image's view
+/// at binding 0 and a sampler filtering with `LaunchConfig::blit_filter` at binding 1. Pipelines
+/// created with an identical layout can use that bind group.
+pub const PRESENT_LAYOUT_ENTRIES: &[wgpu::BindGroupLayoutEntry] = &[
+    wgpu::BindGroupLayoutEntry {
+        binding: 0,
+        visibility: wgpu::ShaderStage::FRAGMENT,
+        ty: wgpu::BindingType::SampledTexture {
+            dimension: wgpu::TextureViewDimension::D2,
+            component_type: wgpu::TextureComponentType::Float,
+            multisampled: false,
+        },
+        count: None,
+    },
+    wgpu::BindGroupLayoutEntry {
+        binding: 1,
+        visibility: wgpu::ShaderStage::FRAGMENT,
+        ty: wgpu::BindingType::Sampler { comparison: false },
+        count: None,
+    },
+];
+
+/// Copies a texture over an entire render target with a fullscreen triangle
+pub(crate) struct Blitter {
+    pipeline: wgpu::RenderPipeline,
+    bind_group_layout: wgpu::BindGroupLayout,
+    sampler: wgpu::Sampler,
+    label: String,
+}
+
+impl Blitter {
+    /// Create a blitter drawing to targets of the given format, sampling its source with
+    /// `filter` and labelling its objects with `label_prefix` followed by "Blit"
+    pub fn new(
+        device: &wgpu::Device,
+        format: wgpu::TextureFormat,
+        filter: wgpu::FilterMode,
+        label_prefix: &str,
+    ) -> Self {
+        let fragment = wgpu::include_spirv!("shaders/blit.frag.spv");
+        Self::with_fragment(
+            device,
+            format,
+            filter,
+            format!("{}Blit", label_prefix),
+            fragment,
+        )
+    }
+
+    /// Create a blitter applying a tone-mapping curve to the source, labelled with
+    /// `label_prefix` followed by "Tone map"
+    pub fn tone_mapper(
+        device: &wgpu::Device,
+        format: wgpu::TextureFormat,
+        filter: wgpu::FilterMode,
+        label_prefix: &str,
+        tone_mapping: ToneMapping,
+    ) -> Self {
+        let fragment = match tone_mapping {
+            ToneMapping::Reinhard => wgpu::include_spirv!("shaders/tonemap_reinhard.frag.spv"),
+            ToneMapping::Aces => wgpu::include_spirv!("shaders/tonemap_aces.frag.spv"),
+        };
+        Self::with_fragment(
+            device,
+            format,
+            filter,
+            format!("{}Tone map", label_prefix),
+            fragment,
+        )
+    }
+
+    /// Create a blitter smoothing the source's edges with FXAA, labelled with `label_prefix`
+    /// followed by "FXAA". It always filters linearly, since FXAA's taps between texels rely on
+    /// it to blend their neighbours.
+    pub fn fxaa(device: &wgpu::Device, format: wgpu::TextureFormat, label_prefix: &str) -> Self {
+        let fragment = wgpu::include_spirv!("shaders/fxaa.frag.spv");
+        Self::with_fragment(
+            device,
+            format,
+            wgpu::FilterMode::Linear,
+            format!("{}FXAA", label_prefix),
+            fragment,
+        )
+    }
+
+    fn with_fragment(
+        device: &wgpu::Device,
+        format: wgpu::TextureFormat,
+        filter: wgpu::FilterMode,
+        label: String,
+        fragment: wgpu::ShaderModuleSource,
+    ) -> Self {
+        let vs_module = device.create_shader_module(wgpu::include_spirv!("shaders/blit.vert.spv"));
+        let fs_module = device.create_shader_module(fragment);
+
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some(&label),
+            entries: PRESENT_LAYOUT_ENTRIES,
+        });
+
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some(&label),
+            push_constant_ranges: &[],
+            bind_group_layouts: &[&bind_group_layout],
+        });
+
+        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some(&label),
+            layout: Some(&pipeline_layout),
+            vertex_stage: wgpu::ProgrammableStageDescriptor {
+                module: &vs_module,
+                entry_point: "main",
+            },
+            fragment_stage: Some(wgpu::ProgrammableStageDescriptor {
+                module: &fs_module,
+                entry_point: "main",
+            }),
+            rasterization_state: Some(wgpu::RasterizationStateDescriptor {
+                front_face: wgpu::FrontFace::Ccw,
+                cull_mode: wgpu::CullMode::None,
+                ..Default::default()
+            }),
+            primitive_topology: wgpu::PrimitiveTopology::TriangleList,
+            color_states: &[wgpu::ColorStateDescriptor {
+                format,
+                color_blend: wgpu::BlendDescriptor::REPLACE,
+                alpha_blend: wgpu::BlendDescriptor::REPLACE,
+                write_mask: wgpu::ColorWrite::ALL,
+            }],
+            depth_stencil_state: None,
+            vertex_state: wgpu::VertexStateDescriptor {
+                index_format: wgpu::IndexFormat::Uint16,
+                vertex_buffers: &[],
+            },
+            sample_count: 1,
+            sample_mask: !0,
+            alpha_to_coverage_enabled: false,
+        });
+
+        Self {
+            pipeline,
+            bind_group_layout,
+            sampler: sampler(
+                device,
+                &label,
+                SamplerConfig {
+                    mag_filter: filter,
+                    min_filter: filter,
+                    ..Default::default()
+                },
+            ),
+            label,
+        }
+    }
+
+    /// Create a bind group used to blit from `source`
+    pub fn bind_group(&self, device: &wgpu::Device, source: &wgpu::TextureView) -> wgpu::BindGroup {
+        device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some(&self.label),
+            layout: &self.bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::TextureView(source),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::Sampler(&self.sampler),
+                },
+            ],
+        })
+    }
+
+    /// Record a pass drawing the bound source over all of `target`
+    pub fn blit(
+        &self,
+        encoder: &mut wgpu::CommandEncoder,
+        bind_group: &wgpu::BindGroup,
+        target: &wgpu::TextureView,
+    ) {
+        let mut rpass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            color_attachments: &[wgpu::RenderPassColorAttachmentDescriptor {
+                attachment: target,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Load,
+                    store: true,
+                },
+            }],
+            depth_stencil_attachment: None,
+        });
+        rpass.set_pipeline(&self.pipeline);
+        rpass.set_bind_group(0, bind_group, &[]);
+        rpass.draw(0..3, 0..1);
+    }
+}
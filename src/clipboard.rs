@@ -0,0 +1,26 @@
+/// Clipboard backend reachable from scenes through `Context::clipboard`
+pub trait Clipboard {
+    /// Current clipboard text, if there is any
+    fn get_text(&mut self) -> Option<String>;
+
+    /// Replace the clipboard contents with `text`
+    fn set_text(&mut self, text: String);
+}
+
+/// Clipboard shared only within this process. winit has no system clipboard access, so set
+/// `LaunchConfig::clipboard` to a system-backed implementation (for example one wrapping
+/// `arboard`) to exchange text with other applications.
+#[derive(Debug, Default, Clone)]
+pub struct LocalClipboard {
+    text: Option<String>,
+}
+
+impl Clipboard for LocalClipboard {
+    fn get_text(&mut self) -> Option<String> {
+        self.text.clone()
+    }
+
+    fn set_text(&mut self, text: String) {
+        self.text = Some(text);
+    }
+}
@@ -0,0 +1,134 @@
+use std::convert::TryInto;
+
+/// Floats per vertex: position then RGBA color
+const VERTEX_FLOATS: usize = 6;
+
+/// Smallest vertex buffer allocated, in bytes, enough for a short line of text
+const MIN_CAPACITY: wgpu::BufferAddress = 4096;
+
+/// Draws alpha-blended triangles with a color per vertex, given in clip space as
+/// `VERTEX_FLOATS` floats each: x, y, then linear RGBA. Backs the crate's built-in overlays.
+/// The vertices are written into a buffer kept across frames, which grows as needed like an
+/// `InstanceBuffer`, so drawing doesn't allocate every frame.
+pub(crate) struct ColoredRenderer {
+    pipeline: wgpu::RenderPipeline,
+    vertices: wgpu::Buffer,
+    capacity: wgpu::BufferAddress,
+    label: String,
+}
+
+impl ColoredRenderer {
+    /// Create a renderer drawing to targets of the given format
+    pub fn new(device: &wgpu::Device, format: wgpu::TextureFormat, label: &str) -> Self {
+        let label = label.to_string();
+        let vs_module =
+            device.create_shader_module(wgpu::include_spirv!("shaders/colored.vert.spv"));
+        let fs_module =
+            device.create_shader_module(wgpu::include_spirv!("shaders/colored.frag.spv"));
+
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some(&label),
+            push_constant_ranges: &[],
+            bind_group_layouts: &[],
+        });
+
+        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some(&label),
+            layout: Some(&pipeline_layout),
+            vertex_stage: wgpu::ProgrammableStageDescriptor {
+                module: &vs_module,
+                entry_point: "main",
+            },
+            fragment_stage: Some(wgpu::ProgrammableStageDescriptor {
+                module: &fs_module,
+                entry_point: "main",
+            }),
+            rasterization_state: Some(wgpu::RasterizationStateDescriptor {
+                front_face: wgpu::FrontFace::Ccw,
+                cull_mode: wgpu::CullMode::None,
+                ..Default::default()
+            }),
+            primitive_topology: wgpu::PrimitiveTopology::TriangleList,
+            color_states: &[wgpu::ColorStateDescriptor {
+                format,
+                color_blend: wgpu::BlendDescriptor {
+                    src_factor: wgpu::BlendFactor::SrcAlpha,
+                    dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
+                    operation: wgpu::BlendOperation::Add,
+                },
+                alpha_blend: wgpu::BlendDescriptor {
+                    src_factor: wgpu::BlendFactor::One,
+                    dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
+                    operation: wgpu::BlendOperation::Add,
+                },
+                write_mask: wgpu::ColorWrite::ALL,
+            }],
+            depth_stencil_state: None,
+            vertex_state: wgpu::VertexStateDescriptor {
+                index_format: wgpu::IndexFormat::Uint16,
+                vertex_buffers: &[wgpu::VertexBufferDescriptor {
+                    stride: (VERTEX_FLOATS * std::mem::size_of::<f32>()) as wgpu::BufferAddress,
+                    step_mode: wgpu::InputStepMode::Vertex,
+                    attributes: &wgpu::vertex_attr_array![0 => Float2, 1 => Float4],
+                }],
+            },
+            sample_count: 1,
+            sample_mask: !0,
+            alpha_to_coverage_enabled: false,
+        });
+
+        Self {
+            pipeline,
+            vertices: create_buffer(device, &label, MIN_CAPACITY),
+            capacity: MIN_CAPACITY,
+            label,
+        }
+    }
+
+    /// Record a pass drawing the triangles over `target`, if there are any. The vertices are
+    /// uploaded in place, so this draws only once per submit.
+    pub fn draw(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        encoder: &mut wgpu::CommandEncoder,
+        target: &wgpu::TextureView,
+        vertices: &[f32],
+    ) {
+        if vertices.is_empty() {
+            return;
+        }
+        let contents: Vec<u8> = vertices.iter().flat_map(|f| f.to_ne_bytes()).collect();
+        let size = contents.len() as wgpu::BufferAddress;
+        if size > self.capacity {
+            self.capacity = size.next_power_of_two();
+            self.vertices = create_buffer(device, &self.label, self.capacity);
+        }
+        queue.write_buffer(&self.vertices, 0, &contents);
+
+        let mut rpass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            color_attachments: &[wgpu::RenderPassColorAttachmentDescriptor {
+                attachment: target,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Load,
+                    store: true,
+                },
+            }],
+            depth_stencil_attachment: None,
+        });
+        rpass.set_pipeline(&self.pipeline);
+        rpass.set_vertex_buffer(0, self.vertices.slice(..size));
+        let vertex_count: u32 = (vertices.len() / VERTEX_FLOATS).try_into().unwrap();
+        rpass.draw(0..vertex_count, 0..1);
+    }
+}
+
+fn create_buffer(device: &wgpu::Device, label: &str, size: wgpu::BufferAddress) -> wgpu::Buffer {
+    device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some(label),
+        size,
+        usage: wgpu::BufferUsage::VERTEX | wgpu::BufferUsage::COPY_DST,
+        mapped_at_creation: false,
+    })
+}
@@ -0,0 +1,390 @@
+use crate::{Clipboard, ErrorHandler, FullscreenMode};
+use std::future::Future;
+use std::path::PathBuf;
+use std::pin::Pin;
+use std::time::Duration;
+use winit::event::VirtualKeyCode;
+
+/// Future resolving to the adapter picked by an `AdapterSelector`
+pub type AdapterFuture<'a> = Pin<Box<dyn Future<Output = wgpu::Adapter> + 'a>>;
+
+/// Chooses the adapter in place of the built-in `request_adapter` call
+pub type AdapterSelector =
+    Box<dyn for<'a> FnOnce(&'a wgpu::Instance, &'a wgpu::Surface) -> AdapterFuture<'a>>;
+
+/// Adjusts the `WindowBuilder` used to open the window
+pub type WindowCustomizer =
+    Box<dyn FnOnce(winit::window::WindowBuilder) -> winit::window::WindowBuilder>;
+
+/// Records extra passes onto each frame's swapchain image and recorded copy, see
+/// `LaunchConfig::post_draw`
+pub type PostDraw = Box<dyn FnMut(&mut wgpu::CommandEncoder, &wgpu::TextureView)>;
+
+/// Observes each swapchain rebuild with its reason and new width and height, see
+/// `LaunchConfig::on_swap_chain_rebuild`
+pub type SwapChainRebuildHook = Box<dyn FnMut(SwapChainRebuildReason, u32, u32)>;
+
+/// Why `launch` rebuilt the swapchain
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SwapChainRebuildReason {
+    /// The swapchain was created for the first frame
+    Initial,
+    /// The window was resized
+    Resized,
+    /// The window moved to a monitor with a different scale factor
+    ScaleFactorChanged,
+    /// Acquiring a frame reported the swapchain outdated or lost
+    Outdated,
+    /// `Context::force_swap_chain_rebuild` was called
+    Requested,
+    /// The surface was recreated, after `Context::simulate_surface_loss`
+    SurfaceLost,
+    /// The surface was recreated as the app resumed from being suspended
+    Resumed,
+}
+
+/// When `launch` draws frames
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RedrawPolicy {
+    /// Draw continuously, as fast as presentation allows
+    Always,
+    /// Draw only after a window event arrives or `Context::invalidate` is called, sleeping in
+    /// between
+    Reactive,
+    /// Draw continuously, at most this many times per second
+    Capped(u32),
+    /// Like `Capped`, at the refresh rate of the monitor the window is on, following it when
+    /// it moves to another. winit doesn't report the mode in use, so this is the fastest the
+    /// monitor offers at its current resolution.
+    CappedToMonitor,
+}
+
+/// Keys for inspecting animations frame by frame, see `LaunchConfig::debug_keys`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DebugKeys {
+    /// Toggles `Context::set_paused`
+    pub pause: VirtualKeyCode,
+    /// While paused, updates and draws a single frame
+    pub step: VirtualKeyCode,
+}
+
+impl Default for DebugKeys {
+    fn default() -> Self {
+        Self {
+            pause: VirtualKeyCode::F9,
+            step: VirtualKeyCode::F10,
+        }
+    }
+}
+
+/// How `launch` smooths jagged edges
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AntiAliasing {
+    None,
+    /// Render the managed pass with this many samples per pixel and resolve it, lowered to a
+    /// power of two within `LaunchConfig::max_sample_count`; pipelines must use
+    /// `Context::sample_count`
+    Msaa(u8),
+    /// Render into an intermediate and smooth its edges with a bundled FXAA pass when drawing
+    /// it onto the swapchain, cheaper than MSAA but softer. Not applied with `hdr`.
+    Fxaa,
+}
+
+/// Curve `LaunchConfig::hdr` maps the scene's colors onto the swapchain with
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ToneMapping {
+    /// `c / (1 + c)`, which never quite reaches white
+    Reinhard,
+    /// Narkowicz's fit of the ACES filmic curve, with more contrast and clipping to white
+    Aces,
+}
+
+/// Settings which can be changed while running through `Context::request_render_settings`,
+/// with the same meaning as their `LaunchConfig` counterparts
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RenderSettings {
+    pub supersample: f32,
+    pub intermediate_format: Option<wgpu::TextureFormat>,
+    /// Changing the sample count changes `Context::sample_count`, so pipelines drawing to the
+    /// managed pass must be rebuilt in `Scene::recreate`
+    pub anti_aliasing: AntiAliasing,
+}
+
+/// Settings for `launch_with_config`
+pub struct LaunchConfig {
+    /// Present mode requested for the swapchain; if the surface doesn't support it, `Mailbox` is
+    /// tried and then `Fifo`, which is always supported, and a warning is logged. `Immediate`
+    /// presents without waiting for vsync, tearing, to measure raw throughput.
+    pub present_mode: wgpu::PresentMode,
+    /// Custom adapter selection, replacing the default request for an adapter compatible with
+    /// the window's surface
+    pub adapter_selector: Option<AdapterSelector>,
+    /// Log the adapter, its backend, and the device's enabled features and limits once the
+    /// device is created, to help diagnose differences between machines
+    pub log_device_info: bool,
+    /// Degrade instead of failing on limited adapters: request the default limits lowered to
+    /// what the adapter supports, and carry on without any `Scene::required_features` it lacks,
+    /// with a warning; the scene should then check `Context::features`. Always applied on the
+    /// GL backend.
+    pub gl_compat: bool,
+    /// Log a single info line once the window is up, summarizing the backend, adapter, window
+    /// size, present mode and swapchain format, for pasting into bug reports
+    pub startup_banner: bool,
+    /// Seed handed to the scene through `Context::seed`, for reproducible procedural content;
+    /// when unset a random one is chosen and logged so the run can be repeated
+    pub seed: Option<u64>,
+    /// While the window is being resized, keep drawing the scene at its previous size and
+    /// stretch that over the window, rebuilding the render targets only once the size has
+    /// settled, rather than rebuilding them on every step of the drag. Requires `launch` to draw
+    /// into an intermediate texture.
+    pub keep_frame_during_resize: bool,
+    /// Draw the scene once and present that image every frame, drawing again only after a
+    /// resize or `Context::invalidate`
+    pub static_image: bool,
+    /// Render the scene at this multiple of the window's resolution and downsample it into the
+    /// swapchain with `blit_filter`; a linear filter averages every source texel at 2.0. 1.0
+    /// disables super-sampling.
+    pub supersample: f32,
+    /// Filter used by every blit `launch` makes from its intermediate texture, when scaling it
+    /// for `supersample` or `keep_frame_during_resize` and when presenting it. `Nearest` keeps
+    /// pixel art sharp; `Linear` smooths it. The FXAA pass of `AntiAliasing::Fxaa` always
+    /// filters linearly, which it relies on.
+    pub blit_filter: wgpu::FilterMode,
+    /// Power preference used to request the default adapter; ignored with an `adapter_selector`
+    pub power_preference: wgpu::PowerPreference,
+    /// Give the managed pass a `DEPTH_FORMAT` depth attachment
+    pub depth_buffer: bool,
+    /// Record `Scene::depth_prepass` into a depth-only pass before the managed pass each frame;
+    /// the depth is cleared there and kept by the managed pass instead
+    pub depth_prepass: bool,
+    /// Depth the managed depth attachment is cleared to each frame. Set 0.0 for reverse-Z, in
+    /// which case `PipelineConfig::new` defaults to testing with `Greater`.
+    pub depth_clear: f32,
+    /// Draw this many throwaway frames to an offscreen target after `Scene::new`, before the
+    /// window shows the first one, so that shader compilation and uploads don't make the first
+    /// frames hitch. The scene isn't updated for them.
+    pub warmup_frames: u32,
+    /// When to draw frames
+    pub redraw: RedrawPolicy,
+    /// Start with drawing paused, until the scene calls `Context::set_paused` from an event hook
+    pub initial_paused: bool,
+    /// Pause and step through frames with these keys, which still reach the scene as well.
+    /// Each step advances by `fixed_dt`, or a 60th of a second without it.
+    pub debug_keys: Option<DebugKeys>,
+    /// Wait for events while `Context::is_hidden` instead of polling continuously. A hidden
+    /// window is never drawn to either way, since it has no area to draw into.
+    pub pause_when_hidden: bool,
+    /// Prepended to the label of every wgpu object the crate creates, to tell them apart from
+    /// the rest of an application's objects in GPU captures
+    pub label_prefix: &'static str,
+    /// Customize the window with any of winit's attributes, such as its title, size or icon.
+    /// Applied after `decorations`, `always_on_top` and `resizable`, so it can override them.
+    pub window_builder: Option<WindowCustomizer>,
+    /// Give the window a title bar and borders; see `Context::set_decorations` to change this
+    /// at runtime
+    pub decorations: bool,
+    /// Keep the window above all others; see `Context::set_always_on_top` to change this at
+    /// runtime
+    pub always_on_top: bool,
+    /// Let the user resize the window; see `Context::set_resizable` to change this at runtime.
+    /// The crate still rebuilds its targets if the size changes anyway, such as when the scale
+    /// factor changes or the window goes fullscreen.
+    pub resizable: bool,
+    /// Start fullscreen on the window's monitor; see `Context::set_fullscreen` to change this
+    /// at runtime
+    pub fullscreen: Option<FullscreenMode>,
+    /// Index of the monitor to start fullscreen on, in the order of
+    /// `Context::available_monitors`, instead of the window's own
+    pub fullscreen_monitor: Option<usize>,
+    /// Swapchain formats in order of preference; the first one the surface supports is used,
+    /// falling back to `Bgra8UnormSrgb`. See `Context::swap_chain_format` for the one chosen.
+    /// wgpu 0.6 panics on an unsupported swapchain format and can't list the supported ones, so
+    /// only `Bgra8UnormSrgb` and `Bgra8Unorm`, which every desktop surface presents, are chosen;
+    /// others, such as `Rgba16Float` or `Rgb10a2Unorm` for HDR displays, are skipped with a
+    /// warning. Recording with `record_to` or `record_video` always uses `Bgra8UnormSrgb`.
+    pub swap_chain_formats: Vec<wgpu::TextureFormat>,
+    /// Render the scene into an intermediate texture of this format, such as `Rgba16Float` for
+    /// HDR, which `Scene::present` then draws onto the swapchain
+    pub intermediate_format: Option<wgpu::TextureFormat>,
+    /// Render into an `HDR_FORMAT` intermediate, taking precedence over `intermediate_format`,
+    /// and tone-map it onto the swapchain with `tone_mapping` unless `Scene::present` draws it
+    pub hdr: bool,
+    /// Tone-mapping curve used with `hdr`
+    pub tone_mapping: ToneMapping,
+    /// Antialiasing applied to the scene
+    pub anti_aliasing: AntiAliasing,
+    /// Highest sample count `AntiAliasing::Msaa` may use; larger requests are lowered to it
+    /// with a warning. wgpu 0.6 can't report which counts an adapter supports, and only fails
+    /// once an unsupported one is drawn with, so this defaults to `PORTABLE_SAMPLE_COUNT`;
+    /// raise it on hardware known to support more.
+    pub max_sample_count: u32,
+    /// Report this as every frame's `Context::dt` instead of the time actually elapsed, making
+    /// the scene's timing deterministic
+    pub fixed_dt: Option<Duration>,
+    /// Longest `Context::dt` reported, so that a long stall (a breakpoint, the window being
+    /// dragged, the app unpausing) doesn't become one huge simulation step
+    pub max_dt: Duration,
+    /// Draw a graph of recent frame times, from `Context::frame_stats`, in the bottom-left
+    /// corner of the window. It's left out of recordings.
+    pub show_frame_graph: bool,
+    /// Append the frame rate, averaged over `Context::frame_stats`, to the window title once a
+    /// second; change the rest of the title with `Context::set_title`
+    pub show_fps_in_title: bool,
+    /// While `Scene::is_ready` is false, clear each frame and draw an animated spinner in place
+    /// of the scene
+    pub loading_indicator: bool,
+    /// Save every presented frame to this directory as a numbered PPM image, stalling each
+    /// frame until it's written. Combine with `fixed_dt` for recordings at a steady rate.
+    pub record_to: Option<PathBuf>,
+    /// Encode every presented frame into this video file, such as `demo.mp4`, by piping them
+    /// into `ffmpeg`, which must be on the `PATH`; the extension picks the container. Frames
+    /// play back at `fixed_dt`, or 60 per second without it, and keep the window's size at
+    /// launch, stretched if it's resized. If `ffmpeg` can't be started, a `LaunchError::Record`
+    /// is reported and the scene runs without recording. Can't be combined with `record_to`.
+    #[cfg(feature = "video")]
+    pub record_video: Option<PathBuf>,
+    /// Name the thread running the event loop, such as "wgpu_launchpad_render", so it's easy to
+    /// find in profiler captures; Linux truncates it to 15 bytes. Supported on Linux and macOS.
+    pub thread_name: Option<String>,
+    /// Raise the priority of the thread running the event loop; on Linux this lowers its nice
+    /// value, which needs the privilege to do so, and elsewhere it only logs a warning
+    #[cfg(feature = "thread_priority")]
+    pub raise_thread_priority: bool,
+    /// Center the window on the primary monitor when it opens
+    pub center_on_start: bool,
+    /// Put the window up before requesting the adapter and device, showing that the GPU is
+    /// initializing in its title and with a busy cursor, so slow device creation isn't spent
+    /// without feedback. Nothing can be drawn into it before the device exists. The events
+    /// winit delivers meanwhile are passed on to the scene once it's created. Only desktop
+    /// platforms can process events this early, so elsewhere the window may stay hidden.
+    pub show_init_status: bool,
+    /// Backend for `Context::clipboard`; defaults to a `LocalClipboard`
+    pub clipboard: Option<Box<dyn Clipboard>>,
+    /// Called every frame once the scene has drawn and the image is on the swapchain, right
+    /// before the frame is submitted, to composite extra passes such as a watermark onto any
+    /// scene without changing it. When recording with `record_to` or `record_video`, it's called
+    /// a second time with the recorded copy of the frame, so both carry the passes. The frame
+    /// graph is drawn over whatever it records.
+    pub post_draw: Option<PostDraw>,
+    /// Called every time the swapchain is rebuilt, for profiling resizes and spotting
+    /// needless rebuilds. Several causes between two frames are reported once, with the first.
+    pub on_swap_chain_rebuild: Option<SwapChainRebuildHook>,
+    /// Skip frames while this many are still being rendered by the GPU, instead of queuing up
+    /// more and adding latency
+    pub max_frames_in_flight: Option<usize>,
+    /// Submit `Scene::compute`'s work together with the frame's in one `Queue::submit`, halving
+    /// the submissions per frame at the cost of the compute work no longer starting before a
+    /// swapchain image is acquired; only affects scenes with `Scene::has_compute`, whose compute
+    /// is otherwise submitted apart. Frames can't be batched with each other, since each must
+    /// be submitted before it's presented.
+    pub single_submit: bool,
+    /// Block before updating the scene until fewer than this many frames are queued on the GPU,
+    /// so that input is read closer to when its frame is shown; 1 waits for the previous frame
+    /// to finish. wgpu 0.6 can't set this on the swapchain, so it's done with fences.
+    pub max_frame_latency: Option<usize>,
+    /// Wait for the GPU to finish every frame, reporting a `LaunchError::FrameTimeout` for any
+    /// frame that takes longer than this, which must be above zero
+    pub frame_timeout: Option<Duration>,
+    /// Receives errors in addition to them being logged
+    pub error_handler: Option<ErrorHandler>,
+}
+
+impl LaunchConfig {
+    /// Defaults for 3D scenes: a depth buffer and the high-performance adapter. Pair it with
+    /// `PipelineConfig::new_3d` for back-face culling.
+    pub fn default_3d() -> Self {
+        Self {
+            power_preference: wgpu::PowerPreference::HighPerformance,
+            depth_buffer: true,
+            ..Self::default()
+        }
+    }
+
+    /// Settings for measuring raw render throughput: drawing continuously with `Immediate`
+    /// presentation, which falls back as described for `present_mode` where it's unsupported
+    pub fn benchmark() -> Self {
+        Self {
+            present_mode: wgpu::PresentMode::Immediate,
+            redraw: RedrawPolicy::Always,
+            ..Self::default()
+        }
+    }
+
+    /// Defaults for 2D scenes: no depth buffer. Pair it with `PipelineConfig::new_2d` for alpha
+    /// blending.
+    pub fn default_2d() -> Self {
+        Self {
+            depth_buffer: false,
+            ..Self::default()
+        }
+    }
+
+    /// Whether presented frames are recorded, with `record_to` or `record_video`
+    pub(crate) fn records(&self) -> bool {
+        #[cfg(feature = "video")]
+        if self.record_video.is_some() {
+            return true;
+        }
+        self.record_to.is_some()
+    }
+}
+
+impl Default for LaunchConfig {
+    fn default() -> Self {
+        Self {
+            present_mode: wgpu::PresentMode::Mailbox,
+            adapter_selector: None,
+            log_device_info: false,
+            gl_compat: false,
+            startup_banner: false,
+            seed: None,
+            keep_frame_during_resize: false,
+            static_image: false,
+            supersample: 1.0,
+            blit_filter: wgpu::FilterMode::Linear,
+            power_preference: wgpu::PowerPreference::Default,
+            depth_buffer: true,
+            depth_prepass: false,
+            depth_clear: 1.0,
+            warmup_frames: 0,
+            redraw: RedrawPolicy::Always,
+            initial_paused: false,
+            debug_keys: None,
+            pause_when_hidden: false,
+            label_prefix: "",
+            window_builder: None,
+            decorations: true,
+            always_on_top: false,
+            resizable: true,
+            fullscreen: None,
+            fullscreen_monitor: None,
+            swap_chain_formats: vec![wgpu::TextureFormat::Bgra8UnormSrgb],
+            intermediate_format: None,
+            hdr: false,
+            tone_mapping: ToneMapping::Aces,
+            anti_aliasing: AntiAliasing::None,
+            max_sample_count: crate::PORTABLE_SAMPLE_COUNT,
+            fixed_dt: None,
+            max_dt: Duration::from_millis(100),
+            show_frame_graph: false,
+            show_fps_in_title: false,
+            loading_indicator: false,
+            record_to: None,
+            #[cfg(feature = "video")]
+            record_video: None,
+            thread_name: None,
+            #[cfg(feature = "thread_priority")]
+            raise_thread_priority: false,
+            center_on_start: false,
+            show_init_status: false,
+            clipboard: None,
+            post_draw: None,
+            on_swap_chain_rebuild: None,
+            max_frames_in_flight: None,
+            single_submit: false,
+            max_frame_latency: None,
+            frame_timeout: None,
+            error_handler: None,
+        }
+    }
+}
@@ -0,0 +1,536 @@
+use crate::{
+    fullscreen, Clipboard, DeviceEvent, DeviceId, FrameStats, FullscreenMode, InputState,
+    MemoryStats, RenderSettings, WindowEvent,
+};
+use std::sync::Arc;
+use std::time::Duration;
+use winit::dpi::{LogicalSize, PhysicalSize, Position, Size};
+use winit::monitor::{MonitorHandle, VideoMode};
+use winit::window::{CursorIcon, Theme, Window};
+
+/// What `Context::force_swap_chain_rebuild` and `Context::simulate_surface_loss` asked for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum SurfaceRebuild {
+    SwapChain,
+    Surface,
+}
+
+/// State maintained by `launch` and handed to the scene's hooks
+pub struct Context {
+    window: Option<Window>,
+    device: Arc<wgpu::Device>,
+    queue: Arc<wgpu::Queue>,
+    title: String,
+    title_fps: Option<f64>,
+    input: InputState,
+    devices: Vec<DeviceId>,
+    invalidated: bool,
+    dt: Duration,
+    frame_index: u64,
+    seed: u64,
+    label_prefix: &'static str,
+    clipboard: Box<dyn Clipboard>,
+    scale_factor: f64,
+    size: PhysicalSize<u32>,
+    theme: Theme,
+    features: wgpu::Features,
+    sample_count: u32,
+    format: wgpu::TextureFormat,
+    swap_chain_format: wgpu::TextureFormat,
+    depth_buffer: bool,
+    depth_prepass: bool,
+    depth_clear: f32,
+    hidden: bool,
+    paused: bool,
+    wireframe: bool,
+    settings_request: Option<RenderSettings>,
+    rebuild_request: Option<SurfaceRebuild>,
+    memory_stats: MemoryStats,
+    frame_stats: FrameStats,
+    #[cfg(feature = "text")]
+    text: crate::TextBrush,
+}
+
+impl Context {
+    pub(crate) fn new(
+        window: Option<Window>,
+        device: Arc<wgpu::Device>,
+        queue: Arc<wgpu::Queue>,
+        clipboard: Box<dyn Clipboard>,
+        sample_count: u32,
+        format: wgpu::TextureFormat,
+    ) -> Self {
+        let scale_factor = window.as_ref().map_or(1.0, Window::scale_factor);
+        Self {
+            device,
+            queue,
+            title: String::new(),
+            title_fps: None,
+            input: InputState::new(scale_factor),
+            devices: Vec::new(),
+            invalidated: false,
+            dt: Duration::default(),
+            frame_index: 0,
+            seed: 0,
+            label_prefix: "",
+            clipboard,
+            scale_factor,
+            size: window
+                .as_ref()
+                .map_or(PhysicalSize::new(0, 0), Window::inner_size),
+            theme: window.as_ref().map_or(Theme::Light, initial_theme),
+            features: wgpu::Features::empty(),
+            sample_count,
+            format,
+            swap_chain_format: format,
+            depth_buffer: true,
+            depth_prepass: false,
+            depth_clear: 1.0,
+            hidden: false,
+            paused: false,
+            wireframe: false,
+            settings_request: None,
+            rebuild_request: None,
+            memory_stats: MemoryStats::default(),
+            frame_stats: FrameStats::default(),
+            #[cfg(feature = "text")]
+            text: crate::TextBrush::default(),
+            window,
+        }
+    }
+
+    /// Shared handle to the device, for creating resources on other threads such as with a
+    /// `Loader`
+    pub fn device(&self) -> &Arc<wgpu::Device> {
+        &self.device
+    }
+
+    /// Shared handle to the queue, for uploads through `Queue::write_buffer` such as
+    /// `InstanceBuffer::write` or `DebugGrid::set_view_projection`. They land before the next
+    /// submit, so data written during `Scene::update` is in place for that frame's draw.
+    pub fn queue(&self) -> &Arc<wgpu::Queue> {
+        &self.queue
+    }
+
+    /// The window being rendered to, or `None` when rendering headless
+    pub fn window(&self) -> Option<&Window> {
+        self.window.as_ref()
+    }
+
+    /// Change the cursor shown while it is over the window
+    pub fn set_cursor(&self, icon: CursorIcon) {
+        if let Some(window) = &self.window {
+            window.set_cursor_icon(icon);
+        }
+    }
+
+    /// Place the IME candidate window near the given position within the window, such as the
+    /// caret of the focused text field. winit 0.22 reports no preedit text, only committed
+    /// characters through `InputState::text`.
+    pub fn set_ime_position(&self, position: Position) {
+        if let Some(window) = &self.window {
+            window.set_ime_position(position);
+        }
+    }
+
+    /// Make the window fullscreen in the given mode, or windowed if `None`
+    pub fn set_fullscreen(&self, mode: Option<FullscreenMode>) {
+        if let Some(window) = &self.window {
+            fullscreen::set_fullscreen(window, mode, None);
+        }
+    }
+
+    /// Make the window fullscreen in the given mode on a monitor from `available_monitors`
+    pub fn set_fullscreen_on(&self, mode: FullscreenMode, monitor: MonitorHandle) {
+        if let Some(window) = &self.window {
+            fullscreen::set_fullscreen(window, Some(mode), Some(monitor));
+        }
+    }
+
+    /// Monitors connected to the system, for `set_fullscreen_on`
+    pub fn available_monitors(&self) -> Vec<MonitorHandle> {
+        self.window
+            .as_ref()
+            .map_or_else(Vec::new, |window| window.available_monitors().collect())
+    }
+
+    /// Leave fullscreen if the window is fullscreen, otherwise enter it in the given mode
+    pub fn toggle_fullscreen(&self, mode: FullscreenMode) {
+        let mode = if self.is_fullscreen() {
+            None
+        } else {
+            Some(mode)
+        };
+        self.set_fullscreen(mode);
+    }
+
+    /// Whether the window is currently fullscreen
+    pub fn is_fullscreen(&self) -> bool {
+        self.window
+            .as_ref()
+            .is_some_and(|window| window.fullscreen().is_some())
+    }
+
+    /// Video modes supported by the window's current monitor, for `FullscreenMode::Exclusive`
+    pub fn video_modes(&self) -> Vec<VideoMode> {
+        self.window.as_ref().map_or_else(Vec::new, |window| {
+            window.current_monitor().video_modes().collect()
+        })
+    }
+
+    /// Title of the window, as set by `LaunchConfig::window_builder` or `set_title`, without
+    /// the frame rate `LaunchConfig::show_fps_in_title` appends
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    /// Change the window's title, keeping the frame rate appended to it if it's shown
+    pub fn set_title(&mut self, title: impl Into<String>) {
+        self.title = title.into();
+        self.apply_title();
+    }
+
+    pub(crate) fn set_title_fps(&mut self, fps: f64) {
+        self.title_fps = Some(fps);
+        self.apply_title();
+    }
+
+    fn apply_title(&self) {
+        if let Some(window) = &self.window {
+            match self.title_fps {
+                Some(fps) => window.set_title(&format!("{} ({:.0} FPS)", self.title, fps)),
+                None => window.set_title(&self.title),
+            }
+        }
+    }
+
+    /// Show or hide the window's title bar and borders
+    pub fn set_decorations(&self, decorations: bool) {
+        if let Some(window) = &self.window {
+            window.set_decorations(decorations);
+        }
+    }
+
+    /// Keep the window above all others, or let it be covered again
+    pub fn set_always_on_top(&self, always_on_top: bool) {
+        if let Some(window) = &self.window {
+            window.set_always_on_top(always_on_top);
+        }
+    }
+
+    /// Allow or prevent the user resizing the window
+    pub fn set_resizable(&self, resizable: bool) {
+        if let Some(window) = &self.window {
+            window.set_resizable(resizable);
+        }
+    }
+
+    /// Features enabled on the device: the scene's required features, plus whichever of its
+    /// optional ones the adapter supports
+    pub fn features(&self) -> wgpu::Features {
+        self.features
+    }
+
+    /// Number of samples per pixel of the render target; pipelines drawing to it must match
+    pub fn sample_count(&self) -> u32 {
+        self.sample_count
+    }
+
+    /// Color format of the render target; pipelines drawing to it must match
+    pub fn format(&self) -> wgpu::TextureFormat {
+        self.format
+    }
+
+    /// Keyboard and mouse state, updated before each event reaches the scene
+    pub fn input(&self) -> &InputState {
+        &self.input
+    }
+
+    /// Input devices currently connected, in the order they were first seen. Not every platform
+    /// announces the devices connected before launch, so those may only appear once they send
+    /// an event.
+    pub fn input_devices(&self) -> &[DeviceId] {
+        &self.devices
+    }
+
+    /// Ratio of physical to logical pixels for the window, kept current as it changes
+    pub fn scale_factor(&self) -> f64 {
+        self.scale_factor
+    }
+
+    /// Size of the window's client area in physical pixels, kept current as it's resized
+    pub fn physical_size(&self) -> PhysicalSize<u32> {
+        self.size
+    }
+
+    /// Size of the window's client area in logical pixels, the physical size divided by
+    /// `scale_factor`, for laying out UI
+    pub fn logical_size(&self) -> LogicalSize<f64> {
+        self.size.to_logical(self.scale_factor)
+    }
+
+    /// Ask for the window's client area to be resized; the new size arrives as a `Resized` event
+    pub fn set_inner_size(&self, size: impl Into<Size>) {
+        if let Some(window) = &self.window {
+            window.set_inner_size(size);
+        }
+    }
+
+    /// System theme, kept current through `WindowEvent::ThemeChanged`
+    pub fn theme(&self) -> Theme {
+        self.theme.clone()
+    }
+
+    /// Whether the window is hidden from view; winit 0.22 has no occlusion events, so this only
+    /// tracks minimization, which shrinks the window to zero size
+    pub fn is_hidden(&self) -> bool {
+        self.hidden
+    }
+
+    /// Format of the images presented to the window, chosen from
+    /// `LaunchConfig::swap_chain_formats`; `Scene::present` draws in this format
+    pub fn swap_chain_format(&self) -> wgpu::TextureFormat {
+        self.swap_chain_format
+    }
+
+    /// Whether the managed pass has a depth attachment, as set by `LaunchConfig::depth_buffer`
+    pub fn depth_buffer(&self) -> bool {
+        self.depth_buffer
+    }
+
+    /// Whether `Scene::depth_prepass` is drawn before the managed pass each frame, as set by
+    /// `LaunchConfig::depth_prepass`
+    pub fn depth_prepass(&self) -> bool {
+        self.depth_prepass
+    }
+
+    /// Depth the managed pass's depth attachment is cleared to, 0.0 for reverse-Z
+    pub fn depth_clear(&self) -> f32 {
+        self.depth_clear
+    }
+
+    /// Stop or resume drawing. While paused, `launch` sleeps until events arrive and passes them
+    /// to the scene's event hooks, but doesn't update or draw it.
+    pub fn set_paused(&mut self, paused: bool) {
+        self.paused = paused;
+    }
+
+    /// Whether drawing is paused by `set_paused` or `LaunchConfig::initial_paused`
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Switch the scene to drawing wireframes, for scenes which pass `wireframe` on to
+    /// `WireframePipeline::get` and `Mesh::draw_as` when they draw
+    pub fn set_wireframe(&mut self, wireframe: bool) {
+        self.wireframe = wireframe;
+    }
+
+    /// Whether `set_wireframe` asked for wireframes
+    pub fn wireframe(&self) -> bool {
+        self.wireframe
+    }
+
+    /// Text to draw over the scene this frame; only drawn by `launch`, and only on frames where
+    /// the scene itself is drawn
+    #[cfg(feature = "text")]
+    pub fn text(&mut self) -> &mut crate::TextBrush {
+        &mut self.text
+    }
+
+    /// Memory used by the render targets managed by `launch`
+    pub fn memory_stats(&self) -> MemoryStats {
+        self.memory_stats
+    }
+
+    /// Times taken by recent frames
+    pub fn frame_stats(&self) -> &FrameStats {
+        &self.frame_stats
+    }
+
+    /// Time elapsed since the previous frame's update
+    pub fn dt(&self) -> Duration {
+        self.dt
+    }
+
+    /// Index of the frame being prepared, starting at 0 and counting presented frames
+    pub fn frame_index(&self) -> u64 {
+        self.frame_index
+    }
+
+    /// Seed for the scene's random number generators, from `LaunchConfig::seed` or randomly
+    /// chosen and logged at launch; from `HeadlessConfig::seed` when rendering headless
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Prefix of the crate's object labels, from `LaunchConfig::label_prefix`; pass it on to the
+    /// crate's helpers which create wgpu objects, such as `Picker::new`
+    pub fn label_prefix(&self) -> &'static str {
+        self.label_prefix
+    }
+
+    /// Clipboard configured through `LaunchConfig::clipboard`
+    pub fn clipboard(&mut self) -> &mut dyn Clipboard {
+        &mut *self.clipboard
+    }
+
+    /// Request that the scene be drawn again when its output is otherwise reused, such as with
+    /// `LaunchConfig::static_image`, or when it would otherwise not be drawn at all under
+    /// `RedrawPolicy::Reactive`
+    pub fn invalidate(&mut self) {
+        self.invalidated = true;
+    }
+
+    /// Change the render settings from the next frame on, rebuilding the render targets and
+    /// calling `Scene::recreate`. Has no effect when rendering headless.
+    pub fn request_render_settings(&mut self, settings: RenderSettings) {
+        crate::assert_supersample(settings.supersample);
+        self.settings_request = Some(settings);
+    }
+
+    pub(crate) fn take_settings_request(&mut self) -> Option<RenderSettings> {
+        self.settings_request.take()
+    }
+
+    /// Rebuild the swapchain and render targets before the next frame, as though it had gone
+    /// out of date, calling `Scene::recreate`; for testing a scene's recreation logic. Has no
+    /// effect when rendering headless.
+    pub fn force_swap_chain_rebuild(&mut self) {
+        self.rebuild_request
+            .get_or_insert(SurfaceRebuild::SwapChain);
+    }
+
+    /// Like `force_swap_chain_rebuild`, but also recreate the window's surface, as happens
+    /// after a mobile app is suspended. The device is kept; wgpu 0.6 has no way to simulate
+    /// losing it.
+    pub fn simulate_surface_loss(&mut self) {
+        self.rebuild_request = Some(SurfaceRebuild::Surface);
+    }
+
+    pub(crate) fn take_rebuild_request(&mut self) -> Option<SurfaceRebuild> {
+        self.rebuild_request.take()
+    }
+
+    pub(crate) fn set_memory_stats(&mut self, memory_stats: MemoryStats) {
+        self.memory_stats = memory_stats;
+    }
+
+    pub(crate) fn frame_stats_mut(&mut self) -> &mut FrameStats {
+        &mut self.frame_stats
+    }
+
+    pub(crate) fn set_sample_count(&mut self, sample_count: u32) {
+        self.sample_count = sample_count;
+    }
+
+    pub(crate) fn set_format(&mut self, format: wgpu::TextureFormat) {
+        self.format = format;
+    }
+
+    pub(crate) fn set_seed(&mut self, seed: u64) {
+        self.seed = seed;
+    }
+
+    pub(crate) fn set_label_prefix(&mut self, label_prefix: &'static str) {
+        self.label_prefix = label_prefix;
+    }
+
+    pub(crate) fn set_features(&mut self, features: wgpu::Features) {
+        self.features = features;
+    }
+
+    pub(crate) fn set_swap_chain_format(&mut self, format: wgpu::TextureFormat) {
+        self.swap_chain_format = format;
+    }
+
+    pub(crate) fn set_depth(&mut self, depth_buffer: bool, depth_prepass: bool, depth_clear: f32) {
+        self.depth_buffer = depth_buffer;
+        self.depth_prepass = depth_prepass;
+        self.depth_clear = depth_clear;
+    }
+
+    pub(crate) fn is_invalidated(&self) -> bool {
+        self.invalidated
+    }
+
+    pub(crate) fn take_invalidated(&mut self) -> bool {
+        std::mem::take(&mut self.invalidated)
+    }
+
+    /// The window, for use by `launch` which always creates one
+    pub(crate) fn expect_window(&self) -> &Window {
+        self.window.as_ref().expect("Context has no window")
+    }
+
+    pub(crate) fn set_size(&mut self, size: PhysicalSize<u32>) {
+        self.size = size;
+    }
+
+    /// Follow the window's size and scale factor, ahead of the scene seeing the event
+    pub(crate) fn track_window(&mut self, event: &WindowEvent) {
+        match event {
+            WindowEvent::Resized(size) => self.size = *size,
+            WindowEvent::ScaleFactorChanged {
+                scale_factor,
+                new_inner_size,
+            } => {
+                self.scale_factor = *scale_factor;
+                self.size = **new_inner_size;
+            }
+            _ => {}
+        }
+    }
+
+    /// Track connected input devices from their events
+    pub(crate) fn track_device(&mut self, device_id: DeviceId, event: &DeviceEvent) {
+        let known = self.devices.iter().position(|&id| id == device_id);
+        match (event, known) {
+            (DeviceEvent::Removed, Some(index)) => {
+                self.devices.remove(index);
+            }
+            (DeviceEvent::Removed, None) | (_, Some(_)) => {}
+            (_, None) => self.devices.push(device_id),
+        }
+    }
+
+    pub(crate) fn set_theme(&mut self, theme: Theme) {
+        self.theme = theme;
+    }
+
+    pub(crate) fn set_hidden(&mut self, hidden: bool) {
+        self.hidden = hidden;
+    }
+
+    pub(crate) fn set_dt(&mut self, dt: Duration) {
+        self.dt = dt;
+    }
+
+    pub(crate) fn next_frame(&mut self) {
+        self.frame_index += 1;
+        self.input.end_frame();
+        #[cfg(feature = "text")]
+        self.text.clear();
+    }
+
+    pub(crate) fn input_mut(&mut self) -> &mut InputState {
+        &mut self.input
+    }
+}
+
+/// Theme of the window at startup; winit only reports the theme on Windows
+#[cfg(target_os = "windows")]
+fn initial_theme(window: &Window) -> Theme {
+    use winit::platform::windows::WindowExtWindows;
+    if window.is_dark_mode() {
+        Theme::Dark
+    } else {
+        Theme::Light
+    }
+}
+
+/// Theme of the window at startup; winit only reports the theme on Windows
+#[cfg(not(target_os = "windows"))]
+fn initial_theme(_window: &Window) -> Theme {
+    Theme::Light
+}
@@ -0,0 +1,131 @@
+use crate::{simple_pipeline, BindGroupBuilder, Context, Matrix4, PipelineConfig, VertexLayout};
+use std::convert::TryInto;
+use wgpu::util::DeviceExt;
+
+/// Name of the grid's objects, after `Context::label_prefix`
+const LABEL: &str = "Debug grid";
+
+/// Color of the grid lines, and of the X, Y and Z axes, in linear RGBA
+const GRID_COLOR: [f32; 4] = [0.4, 0.4, 0.4, 1.0];
+const AXIS_COLORS: [[f32; 4]; 3] = [
+    [1.0, 0.2, 0.2, 1.0],
+    [0.2, 1.0, 0.2, 1.0],
+    [0.2, 0.4, 1.0, 1.0],
+];
+
+/// Bytes in the uniform buffer: the view-projection matrix
+const UNIFORM_SIZE: wgpu::BufferAddress = 64;
+
+/// Ground grid on the XZ plane with a red X axis, green Y axis and blue Z axis, for finding
+/// your way around a 3D scene. Set the camera with `set_view_projection`, then draw it in
+/// `Scene::draw_pass`, where it's depth tested like the rest of the scene.
+pub struct DebugGrid {
+    pipeline: wgpu::RenderPipeline,
+    uniforms: wgpu::Buffer,
+    bind_group: wgpu::BindGroup,
+    vertices: wgpu::Buffer,
+    vertex_count: u32,
+}
+
+impl DebugGrid {
+    /// Create a grid reaching `lines` lines out from the origin in each direction, `spacing`
+    /// apart, with a pipeline for the managed pass as set up by `PipelineConfig::new`
+    pub fn new(ctx: &Context, device: &wgpu::Device, lines: u32, spacing: f32) -> Self {
+        let label = format!("{}{}", ctx.label_prefix(), LABEL);
+        let extent = lines as f32 * spacing;
+        let mut data = Vec::new();
+        let mut line = |from: [f32; 3], to: [f32; 3], color: [f32; 4]| {
+            for &point in &[from, to] {
+                data.extend_from_slice(&point);
+                data.extend_from_slice(&color);
+            }
+        };
+        for i in 1..=lines as i32 {
+            for &offset in &[i as f32 * spacing, -(i as f32) * spacing] {
+                line([-extent, 0.0, offset], [extent, 0.0, offset], GRID_COLOR);
+                line([offset, 0.0, -extent], [offset, 0.0, extent], GRID_COLOR);
+            }
+        }
+        line([-extent, 0.0, 0.0], [extent, 0.0, 0.0], AXIS_COLORS[0]);
+        line([0.0, 0.0, 0.0], [0.0, extent, 0.0], AXIS_COLORS[1]);
+        line([0.0, 0.0, -extent], [0.0, 0.0, extent], AXIS_COLORS[2]);
+
+        let contents: Vec<u8> = data.iter().flat_map(|f| f.to_ne_bytes()).collect();
+        let vertices = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some(&format!("{} vertices", label)),
+            contents: &contents,
+            usage: wgpu::BufferUsage::VERTEX,
+        });
+        let layout = VertexLayout::new()
+            .attr(wgpu::VertexFormat::Float3)
+            .attr(wgpu::VertexFormat::Float4);
+        let vertex_count = (contents.len() as wgpu::BufferAddress / layout.stride())
+            .try_into()
+            .unwrap();
+
+        let uniforms = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some(&format!("{} uniforms", label)),
+            size: UNIFORM_SIZE,
+            usage: wgpu::BufferUsage::UNIFORM | wgpu::BufferUsage::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let (bind_group_layout, bind_group) = BindGroupBuilder::new()
+            .entry(
+                0,
+                wgpu::ShaderStage::VERTEX,
+                wgpu::BindingType::UniformBuffer {
+                    dynamic: false,
+                    min_binding_size: wgpu::BufferSize::new(UNIFORM_SIZE),
+                },
+                wgpu::BindingResource::Buffer(uniforms.slice(..)),
+            )
+            .build(device, Some(&label));
+
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some(&label),
+            bind_group_layouts: &[&bind_group_layout],
+            push_constant_ranges: &[],
+        });
+        let vs_module =
+            device.create_shader_module(wgpu::include_spirv!("shaders/debug_lines.vert.spv"));
+        let fs_module =
+            device.create_shader_module(wgpu::include_spirv!("shaders/colored.frag.spv"));
+        let pipeline = simple_pipeline(
+            device,
+            PipelineConfig {
+                label: Some(LABEL),
+                layout: Some(&pipeline_layout),
+                vertex_buffers: &[layout.descriptor()],
+                topology: wgpu::PrimitiveTopology::LineList,
+                ..PipelineConfig::new(ctx, &vs_module, &fs_module)
+            },
+        );
+
+        Self {
+            pipeline,
+            uniforms,
+            bind_group,
+            vertices,
+            vertex_count,
+        }
+    }
+
+    /// Set the world to clip space transform to draw with, such as
+    /// `OrbitController::view_projection`, with `Context::queue`
+    pub fn set_view_projection(&self, queue: &wgpu::Queue, view_projection: &Matrix4) {
+        let contents: Vec<u8> = view_projection
+            .iter()
+            .flatten()
+            .flat_map(|f| f.to_ne_bytes())
+            .collect();
+        queue.write_buffer(&self.uniforms, 0, &contents);
+    }
+
+    /// Draw the grid and axes
+    pub fn draw<'a>(&'a self, pass: &mut wgpu::RenderPass<'a>) {
+        pass.set_pipeline(&self.pipeline);
+        pass.set_bind_group(0, &self.bind_group, &[]);
+        pass.set_vertex_buffer(0, self.vertices.slice(..));
+        pass.draw(0..self.vertex_count, 0..1);
+    }
+}
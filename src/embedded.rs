@@ -0,0 +1,53 @@
+use crate::{start, EventHandler, LaunchConfig, Scene};
+use winit::event::Event;
+use winit::event_loop::{ControlFlow, EventLoop};
+use winit::platform::desktop::EventLoopExtDesktop;
+
+/// A scene launched with `launch_embedded`, rendered whenever the caller's own loop asks for it
+pub struct Embedded {
+    event_loop: EventLoop<()>,
+    handler: EventHandler,
+    closed: bool,
+}
+
+/// Launch the scene for an application driving its own main loop: rather than handing control
+/// to winit, call `Embedded::render_one_frame` each time a frame is wanted
+pub fn launch_embedded<S: 'static + Scene>(config: LaunchConfig, args: S::Args) -> Embedded {
+    let (event_loop, handler) = start::<S>(config, args, Box::new(|_| {}));
+    Embedded {
+        event_loop,
+        handler,
+        closed: false,
+    }
+}
+
+impl Embedded {
+    /// Process the pending events and go through one iteration of the event loop, which draws
+    /// one frame under `RedrawPolicy::Always`; other policies may skip it. Returns false once
+    /// the window has closed, after which this does nothing.
+    pub fn render_one_frame(&mut self) -> bool {
+        if self.closed {
+            return false;
+        }
+        let handler = &mut self.handler;
+        let closed = &mut self.closed;
+        self.event_loop.run_return(|event, target, control_flow| {
+            // winit sends LoopDestroyed every time run_return returns, so only pass it on once
+            // the scene is done with
+            if let Event::LoopDestroyed = event {
+                if *closed {
+                    handler(event, target, control_flow);
+                }
+                return;
+            }
+            let iteration_done = matches!(event, Event::RedrawEventsCleared);
+            handler(event, target, control_flow);
+            if *control_flow == ControlFlow::Exit {
+                *closed = true;
+            } else if iteration_done {
+                *control_flow = ControlFlow::Exit;
+            }
+        });
+        !self.closed
+    }
+}
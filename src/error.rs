@@ -0,0 +1,45 @@
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Problems `launch` runs into while the scene is running
+#[derive(Debug)]
+pub enum LaunchError {
+    /// A frame took longer than `LaunchConfig::frame_timeout` to finish, so the GPU may be hung
+    FrameTimeout { frame_index: u64, elapsed: Duration },
+    /// A frame could not be acquired from the swapchain
+    SwapChain(wgpu::SwapChainError),
+    /// A frame could not be written out for `LaunchConfig::record_to` or `record_video`, or
+    /// `ffmpeg` could not be started for `record_video`
+    Record(std::io::Error),
+}
+
+impl fmt::Display for LaunchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::FrameTimeout {
+                frame_index,
+                elapsed,
+            } => write!(
+                f,
+                "Frame {} has not finished after {:?}; the GPU may be hung",
+                frame_index, elapsed
+            ),
+            Self::SwapChain(err) => write!(f, "Failed to acquire a frame: {}", err),
+            Self::Record(err) => write!(f, "Failed to record: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for LaunchError {}
+
+/// Called with each error `launch` reports; may run on the watchdog thread
+pub type ErrorHandler = Arc<dyn Fn(&LaunchError) + Send + Sync>;
+
+/// Log an error and pass it on to the configured handler, if any
+pub(crate) fn report(handler: &Option<ErrorHandler>, error: LaunchError) {
+    log::error!("{}", error);
+    if let Some(handler) = handler {
+        handler(&error);
+    }
+}
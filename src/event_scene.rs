@@ -0,0 +1,169 @@
+use crate::{Context, DeviceId, Scene, WindowEvent};
+use std::time::Duration;
+use winit::dpi::PhysicalSize;
+
+/// Everything `launch` tells an `EventScene`, in the order it happens
+pub enum SceneEvent<'a, 'e> {
+    /// The window was resized to this physical size
+    Resized {
+        ctx: &'a mut Context,
+        size: PhysicalSize<u32>,
+    },
+    /// The render target was rebuilt, as in `Scene::recreate`, after a resize or a change of
+    /// `RenderSettings`; resources which depend on it should be rebuilt
+    Recreate {
+        device: &'a wgpu::Device,
+        format: wgpu::TextureFormat,
+        width: u32,
+        height: u32,
+        sample_count: u32,
+    },
+    /// A window event without a variant of its own, such as keyboard or mouse input
+    Input {
+        ctx: &'a mut Context,
+        event: &'a WindowEvent<'e>,
+    },
+    /// Advance the scene by `dt`; sent once per frame right before `Redraw`
+    Update { ctx: &'a mut Context, dt: Duration },
+    /// Draw the frame onto `target`, after `EventScene::draw_pass` has drawn into the managed
+    /// pass
+    Redraw {
+        encoder: &'a mut wgpu::CommandEncoder,
+        target: &'a wgpu::TextureView,
+    },
+    /// The window gained (true) or lost (false) keyboard focus
+    Focus { ctx: &'a mut Context, focused: bool },
+    /// The window was asked to close; `launch` exits after this
+    Close { ctx: &'a mut Context },
+    /// The app was suspended, as in `Scene::suspended`
+    Suspended { ctx: &'a mut Context },
+    /// The app resumed, as in `Scene::resumed`
+    Resumed { ctx: &'a mut Context },
+    /// An input device was connected
+    DeviceAdded {
+        ctx: &'a mut Context,
+        device_id: DeviceId,
+    },
+    /// An input device was disconnected
+    DeviceRemoved {
+        ctx: &'a mut Context,
+        device_id: DeviceId,
+    },
+}
+
+/// Alternative to `Scene` receiving everything through a single `handle` method, for scenes
+/// written as state machines. Launch as `launch::<EventDriven<MyScene>>(args)`.
+pub trait EventScene {
+    /// Arguments passed to the type during launch
+    type Args;
+
+    /// (Optional) device features the scene cannot run without, as in `Scene::required_features`
+    fn required_features() -> wgpu::Features {
+        wgpu::Features::empty()
+    }
+
+    /// (Optional) device features the scene uses if available, as in `Scene::optional_features`
+    fn optional_features() -> wgpu::Features {
+        wgpu::Features::empty()
+    }
+
+    /// Create a new instance of the scene; setup code should use the device to create pipelines
+    fn new(ctx: &mut Context, device: &wgpu::Device, args: Self::Args) -> Self;
+
+    /// React to something happening
+    fn handle(&mut self, event: SceneEvent);
+
+    /// (Optional) draw into the managed render pass, as in `Scene::draw_pass`. The pass borrows
+    /// what's drawn for its whole lifetime, so it can't be handed over as a `SceneEvent`.
+    fn draw_pass<'a>(&'a mut self, _pass: &mut wgpu::RenderPass<'a>) {}
+
+    /// (Optional) color the managed pass is cleared to each frame, as in `Scene::clear_color`
+    fn clear_color(&self) -> wgpu::Color {
+        wgpu::Color::BLACK
+    }
+}
+
+/// Runs an `EventScene` as a `Scene`, translating each hook into a `SceneEvent`
+pub struct EventDriven<S> {
+    pub scene: S,
+}
+
+impl<S: EventScene> Scene for EventDriven<S> {
+    type Args = S::Args;
+
+    fn required_features() -> wgpu::Features {
+        S::required_features()
+    }
+
+    fn optional_features() -> wgpu::Features {
+        S::optional_features()
+    }
+
+    fn new(ctx: &mut Context, device: &wgpu::Device, args: Self::Args) -> Self {
+        Self {
+            scene: S::new(ctx, device, args),
+        }
+    }
+
+    fn update(&mut self, ctx: &mut Context) {
+        let dt = ctx.dt();
+        self.scene.handle(SceneEvent::Update { ctx, dt });
+    }
+
+    fn draw_pass<'a>(&'a mut self, pass: &mut wgpu::RenderPass<'a>) {
+        self.scene.draw_pass(pass);
+    }
+
+    fn draw(&mut self, encoder: &mut wgpu::CommandEncoder, target: &wgpu::TextureView) {
+        self.scene.handle(SceneEvent::Redraw { encoder, target });
+    }
+
+    fn clear_color(&self) -> wgpu::Color {
+        self.scene.clear_color()
+    }
+
+    fn recreate(
+        &mut self,
+        device: &wgpu::Device,
+        format: wgpu::TextureFormat,
+        width: u32,
+        height: u32,
+        sample_count: u32,
+    ) {
+        self.scene.handle(SceneEvent::Recreate {
+            device,
+            format,
+            width,
+            height,
+            sample_count,
+        });
+    }
+
+    fn suspended(&mut self, ctx: &mut Context) {
+        self.scene.handle(SceneEvent::Suspended { ctx });
+    }
+
+    fn resumed(&mut self, ctx: &mut Context) {
+        self.scene.handle(SceneEvent::Resumed { ctx });
+    }
+
+    fn event(&mut self, ctx: &mut Context, event: &WindowEvent) {
+        let event = match *event {
+            WindowEvent::Resized(size) => SceneEvent::Resized { ctx, size },
+            WindowEvent::Focused(focused) => SceneEvent::Focus { ctx, focused },
+            WindowEvent::CloseRequested => SceneEvent::Close { ctx },
+            _ => SceneEvent::Input { ctx, event },
+        };
+        self.scene.handle(event);
+    }
+
+    fn device_added(&mut self, ctx: &mut Context, device_id: DeviceId) {
+        self.scene
+            .handle(SceneEvent::DeviceAdded { ctx, device_id });
+    }
+
+    fn device_removed(&mut self, ctx: &mut Context, device_id: DeviceId) {
+        self.scene
+            .handle(SceneEvent::DeviceRemoved { ctx, device_id });
+    }
+}
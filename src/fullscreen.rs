@@ -0,0 +1,50 @@
+use winit::dpi::PhysicalSize;
+use winit::monitor::{MonitorHandle, VideoMode};
+use winit::window::{Fullscreen, Window};
+
+/// How the window covers the monitor when fullscreen
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FullscreenMode {
+    /// Cover the monitor with a borderless window, leaving its video mode alone
+    Borderless,
+    /// Switch the monitor into a video mode of the given resolution, or its largest one if
+    /// `None`, picking the highest refresh rate available. Falls back to `Borderless` if the
+    /// monitor has no mode of that resolution.
+    Exclusive { size: Option<PhysicalSize<u32>> },
+}
+
+/// Put the window into the given fullscreen mode on `monitor`, or its current monitor if `None`,
+/// or back into a window
+pub(crate) fn set_fullscreen(
+    window: &Window,
+    mode: Option<FullscreenMode>,
+    monitor: Option<MonitorHandle>,
+) {
+    let fullscreen = mode.map(|mode| {
+        let monitor = monitor.unwrap_or_else(|| window.current_monitor());
+        match mode {
+            FullscreenMode::Borderless => Fullscreen::Borderless(monitor),
+            FullscreenMode::Exclusive { size } => match best_video_mode(&monitor, size) {
+                Some(video_mode) => Fullscreen::Exclusive(video_mode),
+                None => {
+                    log::warn!(
+                        "Monitor has no {:?} video mode, falling back to borderless fullscreen",
+                        size
+                    );
+                    Fullscreen::Borderless(monitor)
+                }
+            },
+        }
+    });
+    window.set_fullscreen(fullscreen);
+}
+
+fn best_video_mode(monitor: &MonitorHandle, size: Option<PhysicalSize<u32>>) -> Option<VideoMode> {
+    monitor
+        .video_modes()
+        .filter(|mode| size.is_none_or(|size| mode.size() == size))
+        .max_by_key(|mode| {
+            let area = mode.size().width * mode.size().height;
+            (area, mode.refresh_rate(), mode.bit_depth())
+        })
+}
@@ -0,0 +1,105 @@
+use crate::colored::ColoredRenderer;
+use crate::FrameStats;
+use winit::dpi::PhysicalSize;
+
+/// Width of each frame's bar, in physical pixels
+const BAR_WIDTH: f32 = 2.0;
+
+/// Height of the graph, in physical pixels
+const GRAPH_HEIGHT: f32 = 64.0;
+
+/// Distance of the graph from the bottom-left corner of the window, in physical pixels
+const MARGIN: f32 = 8.0;
+
+/// Frame time reaching the top of the graph, in seconds
+const MAX_FRAME_TIME: f32 = 1.0 / 20.0;
+
+/// Frame times of 60 and 30 frames per second, above which bars turn yellow and red
+const TARGET_FRAME_TIMES: [f32; 2] = [1.0 / 60.0, 1.0 / 30.0];
+
+/// Bar graph of recent frame times drawn in the corner of the window for
+/// `LaunchConfig::show_frame_graph`, with a line marking 60 frames per second. The geometry is a
+/// few hundred vertices built on the CPU each frame, so drawing it barely shows up in the
+/// times it measures.
+pub(crate) struct FrameGraph {
+    renderer: ColoredRenderer,
+}
+
+impl FrameGraph {
+    /// Create a graph drawing to targets of the given format
+    pub fn new(device: &wgpu::Device, format: wgpu::TextureFormat, label_prefix: &str) -> Self {
+        Self {
+            renderer: ColoredRenderer::new(device, format, &format!("{}Frame graph", label_prefix)),
+        }
+    }
+
+    /// Record a pass drawing the graph over `target`
+    pub fn draw(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        encoder: &mut wgpu::CommandEncoder,
+        target: &wgpu::TextureView,
+        window_size: PhysicalSize<u32>,
+        stats: &FrameStats,
+    ) {
+        let mut quads = Quads::new(window_size);
+        let bottom = window_size.height as f32 - MARGIN;
+        let top = bottom - GRAPH_HEIGHT;
+        let width = crate::stats::FRAME_HISTORY as f32 * BAR_WIDTH;
+        quads.push(MARGIN, top, MARGIN + width, bottom, [0.0, 0.0, 0.0, 0.5]);
+
+        for (i, frame_time) in stats.frame_times().enumerate() {
+            let seconds = frame_time.as_secs_f32();
+            let color = if seconds <= TARGET_FRAME_TIMES[0] {
+                [0.1, 0.8, 0.1, 0.9]
+            } else if seconds <= TARGET_FRAME_TIMES[1] {
+                [0.9, 0.8, 0.1, 0.9]
+            } else {
+                [0.9, 0.1, 0.1, 0.9]
+            };
+            let height = (seconds / MAX_FRAME_TIME).min(1.0) * GRAPH_HEIGHT;
+            let left = MARGIN + i as f32 * BAR_WIDTH;
+            quads.push(left, bottom - height, left + BAR_WIDTH, bottom, color);
+        }
+
+        let line = bottom - TARGET_FRAME_TIMES[0] / MAX_FRAME_TIME * GRAPH_HEIGHT;
+        quads.push(MARGIN, line - 0.5, MARGIN + width, line + 0.5, [1.0; 4]);
+
+        self.renderer
+            .draw(device, queue, encoder, target, &quads.vertices);
+    }
+}
+
+/// Builds vertices for `ColoredRenderer` from rectangles in physical pixels
+struct Quads {
+    scale: [f32; 2],
+    vertices: Vec<f32>,
+}
+
+impl Quads {
+    fn new(window_size: PhysicalSize<u32>) -> Self {
+        Self {
+            scale: [
+                2.0 / window_size.width as f32,
+                -2.0 / window_size.height as f32,
+            ],
+            vertices: Vec::new(),
+        }
+    }
+
+    fn push(&mut self, left: f32, top: f32, right: f32, bottom: f32, color: [f32; 4]) {
+        for &(x, y) in &[
+            (left, top),
+            (left, bottom),
+            (right, bottom),
+            (left, top),
+            (right, bottom),
+            (right, top),
+        ] {
+            self.vertices
+                .extend_from_slice(&[x * self.scale[0] - 1.0, y * self.scale[1] + 1.0]);
+            self.vertices.extend_from_slice(&color);
+        }
+    }
+}
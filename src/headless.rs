@@ -0,0 +1,138 @@
+use crate::readback::{bgra_to_rgba, read_texture};
+use crate::target::{create_depth_view, create_texture};
+use crate::{
+    check_required_features, render_scene, supported_sample_count, Context, LocalClipboard,
+    PassTargets, Scene, PORTABLE_SAMPLE_COUNT, SWAPCHAIN_FORMAT,
+};
+use std::sync::Arc;
+use winit::dpi::PhysicalSize;
+
+/// Settings for `launch_headless`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HeadlessConfig {
+    pub width: u32,
+    pub height: u32,
+    /// Samples per pixel; above 1 the scene renders multisampled and is resolved before readback
+    pub sample_count: u32,
+    /// Highest sample count used, as in `LaunchConfig::max_sample_count`
+    pub max_sample_count: u32,
+    /// Prepended to the label of every wgpu object the crate creates
+    pub label_prefix: &'static str,
+    /// Seed handed to the scene through `Context::seed`, as in `LaunchConfig::seed`; when unset
+    /// it's 0 rather than random, so that renders stay reproducible
+    pub seed: Option<u64>,
+}
+
+impl Default for HeadlessConfig {
+    fn default() -> Self {
+        Self {
+            width: 512,
+            height: 512,
+            sample_count: 1,
+            max_sample_count: PORTABLE_SAMPLE_COUNT,
+            label_prefix: "",
+            seed: None,
+        }
+    }
+}
+
+/// Render one frame of the scene without a window, returning its pixels as tightly packed
+/// sRGB RGBA8 rows, top row first
+pub fn launch_headless<S: Scene>(config: HeadlessConfig, args: S::Args) -> Vec<u8> {
+    let size = PhysicalSize::new(config.width, config.height);
+
+    // Initialize wgpu
+    let instance = wgpu::Instance::new(wgpu::BackendBit::PRIMARY);
+    let (device, queue) = futures::executor::block_on(async {
+        let adapter = instance
+            .request_adapter(&wgpu::RequestAdapterOptions {
+                power_preference: wgpu::PowerPreference::Default,
+                compatible_surface: None,
+            })
+            .await
+            .expect("Request adapter");
+
+        check_required_features::<S>(adapter.features(), &adapter.get_info().name, false);
+        let features = (S::required_features() | S::optional_features()) & adapter.features();
+        adapter
+            .request_device(
+                &wgpu::DeviceDescriptor {
+                    features,
+                    limits: wgpu::Limits::default(),
+                    shader_validation: false,
+                },
+                None,
+            )
+            .await
+            .expect("Request device")
+    });
+    let device = Arc::new(device);
+    let queue = Arc::new(queue);
+
+    // The scene draws to a multisampled target when requested, which is then resolved into a
+    // single-sampled texture that can be copied out
+    let output = create_texture(
+        &device,
+        &format!("{}Headless output", config.label_prefix),
+        size,
+        1,
+        SWAPCHAIN_FORMAT,
+        wgpu::TextureUsage::OUTPUT_ATTACHMENT | wgpu::TextureUsage::COPY_SRC,
+    );
+    let output_view = output.create_view(&wgpu::TextureViewDescriptor::default());
+    let sample_count = supported_sample_count(config.sample_count, config.max_sample_count);
+    let multisampled = if sample_count > 1 {
+        let texture = create_texture(
+            &device,
+            &format!("{}Headless multisampled target", config.label_prefix),
+            size,
+            sample_count,
+            SWAPCHAIN_FORMAT,
+            wgpu::TextureUsage::OUTPUT_ATTACHMENT,
+        );
+        Some(texture.create_view(&wgpu::TextureViewDescriptor::default()))
+    } else {
+        None
+    };
+    let targets = PassTargets {
+        depth: Some(create_depth_view(
+            &device,
+            config.label_prefix,
+            size,
+            sample_count,
+        )),
+        multisampled,
+    };
+
+    // Run the scene for a single frame
+    let mut ctx = Context::new(
+        None,
+        device.clone(),
+        queue.clone(),
+        Box::new(LocalClipboard::default()),
+        sample_count,
+        SWAPCHAIN_FORMAT,
+    );
+    ctx.set_features(device.features());
+    ctx.set_label_prefix(config.label_prefix);
+    ctx.set_seed(config.seed.unwrap_or(0));
+    ctx.set_size(size);
+    let mut scene = S::new(&mut ctx, &device, args);
+    scene.update(&mut ctx);
+
+    let mut compute_encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+        label: Some(&format!("{}Headless compute", config.label_prefix)),
+    });
+    scene.compute(&mut compute_encoder);
+    queue.submit(Some(compute_encoder.finish()));
+
+    let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+        label: Some(&format!("{}Headless frame", config.label_prefix)),
+    });
+    render_scene(&mut scene, &mut encoder, &output_view, &targets, &ctx);
+    queue.submit(Some(encoder.finish()));
+
+    let mut pixels = read_texture(&device, &queue, &output, size, 4, config.label_prefix);
+    bgra_to_rgba(&mut pixels);
+    pixels
+}
@@ -0,0 +1,71 @@
+use winit::dpi::{LogicalPosition, PhysicalPosition};
+use winit::event::{ModifiersState, WindowEvent};
+
+/// Input state tracked by `launch` across window events
+#[derive(Debug, Clone)]
+pub struct InputState {
+    modifiers: ModifiersState,
+    text: String,
+    cursor: Option<PhysicalPosition<f64>>,
+    scale_factor: f64,
+}
+
+impl Default for InputState {
+    fn default() -> Self {
+        Self::new(1.0)
+    }
+}
+
+impl InputState {
+    pub(crate) fn new(scale_factor: f64) -> Self {
+        Self {
+            modifiers: ModifiersState::default(),
+            text: String::new(),
+            cursor: None,
+            scale_factor,
+        }
+    }
+
+    /// Modifier keys (Shift, Ctrl, Alt, Logo) currently held down
+    pub fn modifiers(&self) -> ModifiersState {
+        self.modifiers
+    }
+
+    /// Text typed since the previous frame, including characters committed by an IME; control
+    /// characters such as backspace are left out and should be handled as key events
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Cursor position in physical pixels from the window's top-left corner, or `None` while the
+    /// cursor is outside of the window
+    pub fn cursor_physical(&self) -> Option<PhysicalPosition<f64>> {
+        self.cursor
+    }
+
+    /// Cursor position in logical pixels, the physical position divided by the window's scale
+    /// factor, as UI layout usually wants it
+    pub fn cursor_logical(&self) -> Option<LogicalPosition<f64>> {
+        self.cursor
+            .map(|position| position.to_logical(self.scale_factor))
+    }
+
+    /// Update the tracked state from a window event
+    pub(crate) fn handle_event(&mut self, event: &WindowEvent) {
+        match event {
+            WindowEvent::ModifiersChanged(modifiers) => self.modifiers = *modifiers,
+            WindowEvent::ReceivedCharacter(c) if !c.is_control() => self.text.push(*c),
+            WindowEvent::CursorMoved { position, .. } => self.cursor = Some(*position),
+            WindowEvent::CursorLeft { .. } => self.cursor = None,
+            WindowEvent::ScaleFactorChanged { scale_factor, .. } => {
+                self.scale_factor = *scale_factor
+            }
+            _ => {}
+        }
+    }
+
+    /// Clear the state which only lasts a single frame
+    pub(crate) fn end_frame(&mut self) {
+        self.text.clear();
+    }
+}
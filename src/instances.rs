@@ -0,0 +1,130 @@
+use std::convert::TryInto;
+use std::marker::PhantomData;
+
+/// Smallest buffer allocated, in bytes, so the first few writes don't each reallocate
+const MIN_CAPACITY: wgpu::BufferAddress = 256;
+
+/// Plain data which can be uploaded as its raw bytes, standing in for `bytemuck::Pod`.
+///
+/// # Safety
+/// The type must be `#[repr(C)]` (or a primitive or array of them), with no padding bytes and
+/// every bit pattern valid. `Copy` makes it free of pointers the GPU can't follow.
+pub unsafe trait Pod: Copy + 'static {}
+
+macro_rules! impl_pod {
+    ($($ty:ty),*) => {
+        $(unsafe impl Pod for $ty {})*
+    };
+}
+
+impl_pod!(u8, i8, u16, i16, u32, i32, u64, i64, f32, f64);
+
+unsafe impl<T: Pod, const N: usize> Pod for [T; N] {}
+
+/// View a slice of plain data as its bytes
+fn bytes_of<T: Pod>(data: &[T]) -> &[u8] {
+    // Safety: `Pod` types have no padding, so every byte of the slice is initialized
+    unsafe { std::slice::from_raw_parts(data.as_ptr() as *const u8, std::mem::size_of_val(data)) }
+}
+
+/// Vertex buffer of per-instance data which grows as needed, for drawing many copies of a
+/// `Mesh` with `Mesh::draw_instances`. Each `T` is one instance, laid out as the pipeline's
+/// instance-rate vertex buffer descriptor expects, such as one built with `VertexLayout::instance`
+/// whose stride is `size_of::<T>()`.
+pub struct InstanceBuffer<T: Pod> {
+    buffer: wgpu::Buffer,
+    capacity: wgpu::BufferAddress,
+    count: u32,
+    label: String,
+    _instance: PhantomData<T>,
+}
+
+impl<T: Pod> InstanceBuffer<T> {
+    /// Create an empty buffer of instances
+    pub fn new(device: &wgpu::Device, label: &str) -> Self {
+        assert!(
+            std::mem::size_of::<T>() > 0,
+            "Instances must not be zero-sized"
+        );
+        let label = format!("{} instances", label);
+        Self {
+            buffer: create_buffer(device, &label, MIN_CAPACITY),
+            capacity: MIN_CAPACITY,
+            count: 0,
+            label,
+            _instance: PhantomData,
+        }
+    }
+
+    /// Replace the instances with `instances`, reallocating to the next power of two if they
+    /// don't fit. Uploads are padded to `wgpu::COPY_BUFFER_ALIGNMENT`, so any instance size works.
+    pub fn write(&mut self, device: &wgpu::Device, queue: &wgpu::Queue, instances: &[T]) {
+        let bytes = bytes_of(instances);
+        let size = aligned(bytes.len() as wgpu::BufferAddress);
+        if size > self.capacity {
+            self.capacity = aligned(size.next_power_of_two());
+            self.buffer = create_buffer(device, &self.label, self.capacity);
+        }
+        if size == bytes.len() as wgpu::BufferAddress {
+            queue.write_buffer(&self.buffer, 0, bytes);
+        } else {
+            let mut padded = bytes.to_vec();
+            padded.resize(size as usize, 0);
+            queue.write_buffer(&self.buffer, 0, &padded);
+        }
+        self.count = instances.len().try_into().expect("Too many instances");
+    }
+
+    /// Number of instances last written
+    pub fn len(&self) -> u32 {
+        self.count
+    }
+
+    /// Whether there are no instances to draw
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    /// The written instances, for binding as a vertex buffer
+    pub fn slice(&self) -> wgpu::BufferSlice<'_> {
+        let stride = std::mem::size_of::<T>() as wgpu::BufferAddress;
+        self.buffer
+            .slice(..self.count as wgpu::BufferAddress * stride)
+    }
+}
+
+/// Round a size up to the alignment wgpu requires of buffer writes
+fn aligned(size: wgpu::BufferAddress) -> wgpu::BufferAddress {
+    let align = wgpu::COPY_BUFFER_ALIGNMENT;
+    size.div_ceil(align) * align
+}
+
+fn create_buffer(device: &wgpu::Device, label: &str, size: wgpu::BufferAddress) -> wgpu::Buffer {
+    device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some(label),
+        size,
+        usage: wgpu::BufferUsage::VERTEX | wgpu::BufferUsage::COPY_DST,
+        mapped_at_creation: false,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sizes_are_aligned_to_four_bytes() {
+        assert_eq!(aligned(0), 0);
+        assert_eq!(aligned(6), 8);
+        assert_eq!(aligned(12), 12);
+        assert_eq!(aligned(18), 20);
+    }
+
+    #[test]
+    fn bytes_cover_every_instance() {
+        let instances = [[1_u16, 2, 3], [4, 5, 6], [7, 8, 9]];
+        let bytes = bytes_of(&instances);
+        assert_eq!(bytes.len(), 18);
+        assert_eq!(&bytes[6..8], &4_u16.to_ne_bytes());
+    }
+}
@@ -1,86 +1,786 @@
+/// Build the items only on desktop platforms, the only ones where winit can run its event loop
+/// piecemeal; the items of an `else` block build on every other platform instead
+macro_rules! cfg_desktop {
+    (@cfg $desktop:meta; { $($item:item)* } else { $($other:item)* }) => {
+        $(#[cfg($desktop)] $item)*
+        $(#[cfg(not($desktop))] $other)*
+    };
+    ({ $($item:item)* } else { $($other:item)* }) => {
+        cfg_desktop! {
+            @cfg any(
+                windows,
+                target_os = "macos",
+                target_os = "linux",
+                target_os = "dragonfly",
+                target_os = "freebsd",
+                target_os = "netbsd",
+                target_os = "openbsd"
+            );
+            { $($item)* } else { $($other)* }
+        }
+    };
+    ($($item:item)*) => {
+        cfg_desktop! { { $($item)* } else {} }
+    };
+}
+
+mod bind_group;
+mod blit;
+mod clipboard;
+mod colored;
+mod config;
+mod context;
+mod debug_grid;
+cfg_desktop! {
+    mod embedded;
+}
+mod error;
+mod event_scene;
+mod fullscreen;
+mod graph;
+mod headless;
+mod input;
+mod instances;
+mod loader;
+mod mesh;
+mod orbit;
+mod overlay;
+mod pacing;
+mod picking;
+mod pipeline;
+mod push_constants;
+mod readback;
+mod recording;
+mod sampler;
+mod spinner;
+mod stats;
+mod swap_chain;
+mod target;
+#[cfg(feature = "text")]
+mod text;
+mod texture;
+mod thread;
+mod threaded;
+mod vertex_layout;
+#[cfg(feature = "video")]
+mod video;
+mod viewport;
+mod watchdog;
+
+pub use bind_group::BindGroupBuilder;
+use blit::Blitter;
+pub use blit::PRESENT_LAYOUT_ENTRIES;
+pub use clipboard::{Clipboard, LocalClipboard};
+pub use config::{
+    AdapterFuture, AdapterSelector, AntiAliasing, DebugKeys, LaunchConfig, PostDraw, RedrawPolicy,
+    RenderSettings, SwapChainRebuildHook, SwapChainRebuildReason, ToneMapping, WindowCustomizer,
+};
+pub use context::Context;
+use context::SurfaceRebuild;
+pub use debug_grid::DebugGrid;
+cfg_desktop! {
+    pub use embedded::{launch_embedded, Embedded};
+}
+pub use error::{ErrorHandler, LaunchError};
+pub use event_scene::{EventDriven, EventScene, SceneEvent};
+pub use fullscreen::FullscreenMode;
+pub use headless::{launch_headless, HeadlessConfig};
+pub use input::InputState;
+pub use instances::{InstanceBuffer, Pod};
+pub use loader::Loader;
+pub use mesh::Mesh;
+pub use orbit::{Matrix4, OrbitController};
+pub use overlay::Overlay;
+pub use picking::Picker;
+pub use pipeline::{simple_pipeline, BlendMode, DepthBias, PipelineConfig, WireframePipeline};
+pub use push_constants::PushConstants;
+use recording::{Recorder, Sink};
+pub use sampler::{sampler, SamplerConfig};
+pub use stats::{FrameStats, MemoryStats};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use target::create_depth_view;
+#[cfg(feature = "text")]
+pub use text::TextBrush;
+pub use texture::{linear_view, srgb_view, write_texture_padded};
+pub use threaded::{Simulation, Threaded, ThreadedScene};
+pub use vertex_layout::VertexLayout;
+pub use viewport::Viewport;
+use watchdog::Watchdog;
 pub use wgpu;
-pub use winit::event::WindowEvent;
+pub use winit;
+pub use winit::event::{DeviceEvent, DeviceId, ModifiersState, WindowEvent};
 use winit::{
-    event::Event,
-    event_loop::{ControlFlow, EventLoop},
+    event::{ElementState, Event},
+    event_loop::{ControlFlow, EventLoop, EventLoopWindowTarget},
 };
 
 const SWAPCHAIN_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Bgra8UnormSrgb;
 
-/// Traits implemented by the rendered Scene 
+/// How long the window must go without resizing before `LaunchConfig::keep_frame_during_resize`
+/// rebuilds the render targets
+const RESIZE_SETTLE_TIME: Duration = Duration::from_millis(100);
+
+/// Frame rate `RedrawPolicy::CappedToMonitor` caps to on monitors not reporting their refresh rate
+const FALLBACK_REFRESH_RATE: u32 = 60;
+
+/// How often `LaunchConfig::show_fps_in_title` refreshes the title, slow enough to be readable
+const FPS_TITLE_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Highest MSAA sample count every adapter supports
+pub const PORTABLE_SAMPLE_COUNT: u32 = 4;
+
+/// Time a frame stepped with `LaunchConfig::debug_keys` advances by, unless `fixed_dt` is set
+const STEP_DT: Duration = Duration::from_nanos(16_666_667);
+
+/// Format of the depth attachment used by the managed render pass; pipelines drawn in
+/// `Scene::draw_pass` must use this as their depth-stencil format
+pub const DEPTH_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Depth32Float;
+
+/// Format of the intermediate the scene renders into with `LaunchConfig::hdr`
+pub const HDR_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba16Float;
+
+/// Traits implemented by the rendered Scene
 pub trait Scene {
     /// Arguments passed to the type during launch
     type Args;
 
+    /// (Optional) device features the scene cannot run without; `launch` and `launch_headless`
+    /// fail with a list of the missing ones if the adapter lacks any of them
+    fn required_features() -> wgpu::Features {
+        wgpu::Features::empty()
+    }
+
+    /// (Optional) device features the scene uses only if the adapter has them; see
+    /// `Context::features` for the ones granted
+    fn optional_features() -> wgpu::Features {
+        wgpu::Features::empty()
+    }
+
     /// Create a new instance of the scene; setup code should use the device to create pipelines
-    fn new(device: &wgpu::Device, args: Self::Args) -> Self;
+    fn new(ctx: &mut Context, device: &wgpu::Device, args: Self::Args) -> Self;
+
+    /// (Optional) advance the scene; called once per frame right before drawing
+    fn update(&mut self, _ctx: &mut Context) {}
+
+    /// (Optional) record compute work for this frame. With `has_compute`, it's submitted in its
+    /// own command buffer ahead of the frame's drawing, so that it can start before a swapchain
+    /// image is acquired and wgpu can schedule it apart from the render submission, unless
+    /// `LaunchConfig::single_submit` holds it back for the frame's submission. Otherwise it's
+    /// recorded at the start of the frame's own command buffer. wgpu exposes a single queue, so
+    /// the compute work is always ordered before everything `draw` records.
+    fn compute(&mut self, _encoder: &mut wgpu::CommandEncoder) {}
+
+    /// (Optional) whether `compute` records enough work to be worth a command buffer of its own,
+    /// submitted ahead of the frame
+    fn has_compute(&self) -> bool {
+        false
+    }
+
+    /// (Optional) called once the swapchain image for frame `frame_index` has been acquired,
+    /// before anything is drawn, such as to pick resources kept per swapchain image. `view` is
+    /// the swapchain image itself, not the intermediate the scene may be drawing into.
+    fn frame_begin(&mut self, _view: &wgpu::TextureView, _frame_index: u64) {}
+
+    /// (Optional) with `LaunchConfig::depth_prepass`, draw into a depth-only pass recorded before
+    /// the managed pass, which clears the depth attachment to `Context::depth_clear`. The managed
+    /// pass then keeps that depth, for a prepass filling it ahead of shading.
+    fn depth_prepass<'a>(&'a mut self, _pass: &mut wgpu::RenderPass<'a>) {}
+
+    /// (Optional) draw into the render pass managed by `launch`, which has already been cleared
+    /// to `clear_color()` and, unless `LaunchConfig::depth_buffer` is off, has a `DEPTH_FORMAT`
+    /// depth attachment cleared to `Context::depth_clear`, or holding the depth prepass
+    fn draw_pass<'a>(&'a mut self, _pass: &mut wgpu::RenderPass<'a>) {}
+
+    /// (Optional) draw the scene with full control over the encoder; called every frame after
+    /// the managed pass, so anything recorded here lands on top of `draw_pass`
+    fn draw(&mut self, _encoder: &mut wgpu::CommandEncoder, _target: &wgpu::TextureView) {}
+
+    /// (Optional) finish per-frame bookkeeping, such as recalling a staging belt; called at the
+    /// end of every iteration of the event loop, after any frame has been submitted
+    fn frame_end(&mut self) {}
+
+    /// (Optional) whether the scene has finished loading. With `LaunchConfig::loading_indicator`,
+    /// frames show a spinner instead of the scene until this returns true; `update` still runs.
+    fn is_ready(&self) -> bool {
+        true
+    }
 
-    /// Draw the scene; called every frame
-    fn draw(&mut self, encoder: &mut wgpu::CommandEncoder, target: &wgpu::TextureView);
+    /// (Optional) color the managed pass is cleared to each frame
+    fn clear_color(&self) -> wgpu::Color {
+        wgpu::Color::BLACK
+    }
+
+    /// (Optional) usages the swapchain images need besides `OUTPUT_ATTACHMENT`, which is always
+    /// included; checked whenever the swapchain is created
+    fn surface_usage(&self) -> wgpu::TextureUsage {
+        wgpu::TextureUsage::OUTPUT_ATTACHMENT
+    }
+
+    /// (Optional) adjust the swapchain descriptor right before each time the swapchain is
+    /// created, such as to change its present mode or usage. The format must stay
+    /// `Context::swap_chain_format`, which the crate's own pipelines are built for; choose it
+    /// with `LaunchConfig::swap_chain_formats` instead. wgpu 0.6's descriptor has no alpha mode:
+    /// the swapchain is always composited as opaque.
+    fn configure_surface(&self, _desc: &mut wgpu::SwapChainDescriptor) {}
+
+    /// (Optional) draw the intermediate image onto the swapchain image `target`, such as to
+    /// tone-map an HDR `LaunchConfig::intermediate_format`. `source` binds the intermediate as
+    /// laid out by `PRESENT_LAYOUT_ENTRIES`. Return false to have it copied over unchanged.
+    fn present(
+        &mut self,
+        _encoder: &mut wgpu::CommandEncoder,
+        _source: &wgpu::BindGroup,
+        _target: &wgpu::TextureView,
+    ) -> bool {
+        false
+    }
+
+    /// (Optional) rebuild resources which depend on the render target, called after the window
+    /// is resized or `Context::request_render_settings` takes effect. `width` and `height` are
+    /// the size of the target `draw` renders to, which the supersample factor scales up.
+    fn recreate(
+        &mut self,
+        _device: &wgpu::Device,
+        _format: wgpu::TextureFormat,
+        _width: u32,
+        _height: u32,
+        _sample_count: u32,
+    ) {
+    }
+
+    /// (Optional) react to the app being suspended, such as when it's sent to the background on
+    /// Android; nothing is drawn until it resumes
+    fn suspended(&mut self, _ctx: &mut Context) {}
+
+    /// (Optional) react to the app resuming after being suspended, once the surface has been
+    /// recreated; mobile platforms also resume once at startup
+    fn resumed(&mut self, _ctx: &mut Context) {}
 
     /// (Optional) handle events from Winit
-    fn event(&mut self, _event: &WindowEvent) {}
+    fn event(&mut self, _ctx: &mut Context, _event: &WindowEvent) {}
+
+    /// (Optional) see every event from winit's event loop, before `launch` handles it; an escape
+    /// hatch for anything the other hooks don't cover
+    fn raw_event(&mut self, _event: &winit::event::Event<()>) {}
+
+    /// (Optional) handle raw device events from Winit, such as mouse motion or physical key
+    /// scancodes, which arrive even while the window is unfocused
+    fn device_event(&mut self, _ctx: &mut Context, _device_id: DeviceId, _event: &DeviceEvent) {}
+
+    /// (Optional) react to an input device being connected, once it's in
+    /// `Context::input_devices`; called before `device_event`
+    fn device_added(&mut self, _ctx: &mut Context, _device_id: DeviceId) {}
+
+    /// (Optional) react to an input device being disconnected, once it's been removed from
+    /// `Context::input_devices`; called before `device_event`
+    fn device_removed(&mut self, _ctx: &mut Context, _device_id: DeviceId) {}
 }
 
-/// Launch the scene. See `examples/triangle.rs`.
+/// Launch the scene with the default configuration. See `examples/triangle.rs`.
 pub fn launch<S: 'static + Scene>(args: S::Args) {
+    launch_with_config::<S>(LaunchConfig::default(), args)
+}
+
+/// Launch the scene with the given configuration
+pub fn launch_with_config<S: 'static + Scene>(config: LaunchConfig, args: S::Args) {
+    launch_with_exit::<S, _>(config, args, |_| {})
+}
+
+/// Launch the scene with the given configuration, handing it to `on_exit` once the window has
+/// closed, such as to save results gathered during the session. winit's event loop never
+/// returns, so this is the last thing that runs before the process exits.
+pub fn launch_with_exit<S, F>(config: LaunchConfig, args: S::Args, on_exit: F)
+where
+    S: 'static + Scene,
+    F: 'static + FnOnce(&S),
+{
+    let (event_loop, handler) = start::<S>(config, args, Box::new(on_exit));
+    event_loop.run(handler)
+}
+
+/// Open the window and initialize wgpu and the scene, returning the event loop along with the
+/// handler `launch` runs it with
+pub(crate) fn start<S: 'static + Scene>(
+    mut config: LaunchConfig,
+    args: S::Args,
+    on_exit: Box<dyn FnOnce(&S)>,
+) -> (EventLoop<()>, EventHandler) {
+    thread::configure_current(&config);
+
     // Initialize winit
-    let event_loop = EventLoop::new();
-    let window = winit::window::Window::new(&event_loop).unwrap();
+    let mut event_loop = EventLoop::new();
+    let (window, title) = open_window(&event_loop, &mut config);
+    let startup_events = if config.show_init_status {
+        show_init_status(&mut event_loop, &window, &title)
+    } else {
+        Vec::new()
+    };
 
     // Initialize wgpu
     let instance = wgpu::Instance::new(wgpu::BackendBit::PRIMARY);
     let surface = unsafe { instance.create_surface(&window) };
 
-    let (mut device, queue) = futures::executor::block_on(async {
-        let adapter = instance
-            .request_adapter(&wgpu::RequestAdapterOptions {
-                power_preference: wgpu::PowerPreference::Default,
-                compatible_surface: Some(&surface),
-            })
-            .await
-            .expect("Request adapter");
+    let (device, queue, adapter_info) = futures::executor::block_on(async {
+        let adapter = match config.adapter_selector.take() {
+            Some(selector) => selector(&instance, &surface).await,
+            None => instance
+                .request_adapter(&wgpu::RequestAdapterOptions {
+                    power_preference: config.power_preference,
+                    compatible_surface: Some(&surface),
+                })
+                .await
+                .expect("Request adapter"),
+        };
+
+        let info = adapter.get_info();
+        let compat = config.gl_compat || info.backend == wgpu::Backend::Gl;
+        check_required_features::<S>(adapter.features(), &info.name, compat);
+        let features = (S::required_features() | S::optional_features()) & adapter.features();
+        let limits = if compat {
+            compat_limits(adapter.limits())
+        } else {
+            wgpu::Limits::default()
+        };
 
-        adapter
+        let (device, queue) = adapter
             .request_device(
                 &wgpu::DeviceDescriptor {
-                    features: wgpu::Features::empty(),
-                    limits: wgpu::Limits::default(),
+                    features,
+                    limits,
                     shader_validation: false,
                 },
                 None,
             )
             .await
-            .expect("Request device")
+            .expect("Request device");
+        if config.log_device_info {
+            log_device_info(&info, &device);
+            log::debug!("Adapter features: {:?}", adapter.features());
+            log::debug!("Adapter limits: {:?}", adapter.limits());
+        }
+        (device, queue, info)
     });
 
-    let mut swap_chain = {
-        let size = window.inner_size();
-
-        device.create_swap_chain(
-            &surface,
-            &wgpu::SwapChainDescriptor {
-                usage: wgpu::TextureUsage::OUTPUT_ATTACHMENT,
-                format: SWAPCHAIN_FORMAT,
-                width: size.width,
-                height: size.height,
-                present_mode: wgpu::PresentMode::Mailbox,
-            },
-        )
+    let gpu = Gpu {
+        instance,
+        surface,
+        device: Arc::new(device),
+        queue: Arc::new(queue),
+        adapter_info,
+    };
+    if config.show_init_status {
+        window.set_cursor_icon(winit::window::CursorIcon::Default);
+    }
+    let handler = event_handler::<S>(config, args, window, title, gpu, on_exit);
+    (event_loop, replay_startup_events(handler, startup_events))
+}
+
+/// Mark the window as initializing through its title and cursor, and process its pending events
+/// so that it's up on screen before the thread blocks on requesting the device. The title is
+/// restored once the scene's context takes over the window. Returns the events processed, for
+/// `replay_startup_events`.
+fn show_init_status(
+    event_loop: &mut EventLoop<()>,
+    window: &winit::window::Window,
+    title: &str,
+) -> Vec<StartupEvent> {
+    if title.is_empty() {
+        window.set_title("Initializing GPU...");
+    } else {
+        window.set_title(&format!("{} (initializing GPU...)", title));
+    }
+    window.set_cursor_icon(winit::window::CursorIcon::Progress);
+    pump_startup_events(event_loop)
+}
+
+/// Event received before the handler existed, such as the window's initial `Resized`
+enum StartupEvent {
+    Event(Event<'static, ()>),
+    /// Borrows the size it reports, so it can't be kept as an `Event`
+    ScaleFactorChanged {
+        window_id: winit::window::WindowId,
+        scale_factor: f64,
+        new_inner_size: winit::dpi::PhysicalSize<u32>,
+    },
+}
+
+cfg_desktop! {
+    {
+        /// Run the event loop through one iteration, keeping the window and device events it
+        /// delivers
+        fn pump_startup_events(event_loop: &mut EventLoop<()>) -> Vec<StartupEvent> {
+            use winit::platform::desktop::EventLoopExtDesktop;
+            let mut events = Vec::new();
+            event_loop.run_return(|event, _, control_flow| match event {
+                Event::RedrawEventsCleared => *control_flow = ControlFlow::Exit,
+                Event::WindowEvent {
+                    window_id,
+                    event:
+                        WindowEvent::ScaleFactorChanged {
+                            scale_factor,
+                            new_inner_size,
+                        },
+                } => events.push(StartupEvent::ScaleFactorChanged {
+                    window_id,
+                    scale_factor,
+                    new_inner_size: *new_inner_size,
+                }),
+                Event::WindowEvent { .. }
+                | Event::DeviceEvent { .. }
+                | Event::Suspended
+                | Event::Resumed => events.extend(event.to_static().map(StartupEvent::Event)),
+                _ => {}
+            });
+            events
+        }
+    } else {
+        /// Nothing is processed elsewhere, so the window may stay hidden until the device is
+        /// ready
+        fn pump_startup_events(_event_loop: &mut EventLoop<()>) -> Vec<StartupEvent> {
+            Vec::new()
+        }
+    }
+}
+
+/// Pass the events received before the handler existed on to it once the event loop starts, so
+/// that the scene sees them too
+fn replay_startup_events(mut handler: EventHandler, events: Vec<StartupEvent>) -> EventHandler {
+    if events.is_empty() {
+        return handler;
+    }
+    let mut pending = Some(events);
+    Box::new(move |event, target, control_flow| {
+        // The loop starts with `NewEvents`, which the replayed events belong after
+        let mut event = Some(event);
+        if let Some(Event::NewEvents(_)) = event {
+            handler(event.take().unwrap(), target, control_flow);
+        }
+        for startup_event in pending.take().into_iter().flatten() {
+            match startup_event {
+                StartupEvent::Event(event) => handler(event, target, control_flow),
+                StartupEvent::ScaleFactorChanged {
+                    window_id,
+                    scale_factor,
+                    mut new_inner_size,
+                } => handler(
+                    Event::WindowEvent {
+                        window_id,
+                        event: WindowEvent::ScaleFactorChanged {
+                            scale_factor,
+                            new_inner_size: &mut new_inner_size,
+                        },
+                    },
+                    target,
+                    control_flow,
+                ),
+            }
+        }
+        if let Some(event) = event {
+            handler(event, target, control_flow);
+        }
+    })
+}
+
+/// Launch the scene on a device created elsewhere, such as by another library sharing textures
+/// with the scene. `instance` must be the one the device's adapter came from, since the
+/// window's surface is created from it; `adapter_info` is only used for logging. The scene's
+/// required features must already be enabled on the device, and `adapter_selector` and
+/// `power_preference` are ignored.
+pub fn launch_with_device<S: 'static + Scene>(
+    config: LaunchConfig,
+    instance: wgpu::Instance,
+    device: Arc<wgpu::Device>,
+    queue: Arc<wgpu::Queue>,
+    adapter_info: wgpu::AdapterInfo,
+    args: S::Args,
+) {
+    check_required_features::<S>(device.features(), &adapter_info.name, false);
+    if config.log_device_info {
+        log_device_info(&adapter_info, &device);
+    }
+
+    let mut config = config;
+    thread::configure_current(&config);
+    let event_loop = EventLoop::new();
+    let (window, title) = open_window(&event_loop, &mut config);
+    let surface = unsafe { instance.create_surface(&window) };
+    let gpu = Gpu {
+        instance,
+        surface,
+        device,
+        queue,
+        adapter_info,
     };
+    let handler = event_handler::<S>(config, args, window, title, gpu, Box::new(|_| {}));
+    event_loop.run(handler)
+}
+
+/// Handles every event of winit's event loop, drawing the scene as it goes
+pub(crate) type EventHandler =
+    Box<dyn FnMut(Event<'_, ()>, &EventLoopWindowTarget<()>, &mut ControlFlow)>;
+
+/// The wgpu objects `launch` renders with
+struct Gpu {
+    instance: wgpu::Instance,
+    surface: wgpu::Surface,
+    device: Arc<wgpu::Device>,
+    queue: Arc<wgpu::Queue>,
+    adapter_info: wgpu::AdapterInfo,
+}
+
+/// Open the window as configured, returning it along with its title
+fn open_window(
+    event_loop: &EventLoop<()>,
+    config: &mut LaunchConfig,
+) -> (winit::window::Window, String) {
+    let mut window_builder = winit::window::WindowBuilder::new()
+        .with_decorations(config.decorations)
+        .with_always_on_top(config.always_on_top)
+        .with_resizable(config.resizable);
+    if let Some(customize) = config.window_builder.take() {
+        window_builder = customize(window_builder);
+    }
+    let title = window_builder.window.title.clone();
+    let window = window_builder.build(event_loop).unwrap();
+    if config.center_on_start {
+        center_window(&window);
+    }
+    if config.fullscreen.is_some() {
+        let monitor = config.fullscreen_monitor.and_then(|index| {
+            let monitor = window.available_monitors().nth(index);
+            if monitor.is_none() {
+                log::warn!("There is no monitor {}, using the window's own", index);
+            }
+            monitor
+        });
+        fullscreen::set_fullscreen(&window, config.fullscreen, monitor);
+    }
+    (window, title)
+}
+
+/// Set up the scene and everything it draws with, returning the handler which runs it for each
+/// of the event loop's events until the window closes
+fn event_handler<S: 'static + Scene>(
+    mut config: LaunchConfig,
+    args: S::Args,
+    window: winit::window::Window,
+    title: String,
+    gpu: Gpu,
+    on_exit: Box<dyn FnOnce(&S)>,
+) -> EventHandler {
+    let Gpu {
+        instance,
+        mut surface,
+        device,
+        queue,
+        adapter_info,
+    } = gpu;
+
+    // The swapchain is only created on the first frame, once the window has been shown and its
+    // size is final; the other targets are rebuilt then if the size has changed
+    let mut swap_chain: Option<wgpu::SwapChain> = None;
+    let mut target_size = window.inner_size();
+    let size = target_size;
+    assert_supersample(config.supersample);
+    assert!(
+        config.redraw != RedrawPolicy::Capped(0),
+        "Frame rate cap must be above zero"
+    );
+    assert!(
+        config.frame_timeout != Some(Duration::from_secs(0)),
+        "Frame timeout must be above zero"
+    );
+    let render_size = scaled_size(size, config.supersample);
+    // Clamped once here, and again only if the render settings change it
+    let mut sample_count = configured_sample_count(&config);
     let mut resized = false;
+    // Set by whatever first asks for the swapchain to be rebuilt before the next frame
+    let mut rebuild_reason = Some(SwapChainRebuildReason::Initial);
+    let mut resize_settles_at: Option<Instant> = None;
+    let mut suspended = false;
+
+    let swap_chain_format = choose_swap_chain_format(&config);
+
+    #[allow(unused_mut)]
+    let mut sink = config.record_to.take().map(Sink::Frames);
+    #[cfg(feature = "video")]
+    if let Some(path) = config.record_video.take() {
+        assert!(
+            sink.is_none(),
+            "record_to and record_video can't be combined"
+        );
+        let frame_rate = config
+            .fixed_dt
+            .map_or(1.0 / STEP_DT.as_secs_f64(), |dt| 1.0 / dt.as_secs_f64());
+        match video::VideoEncoder::spawn(&path, size, frame_rate) {
+            Ok(encoder) => sink = Some(Sink::Video(encoder)),
+            Err(err) => {
+                error::report(&config.error_handler, LaunchError::Record(err));
+                log::warn!("Couldn't start ffmpeg, continuing without recording");
+            }
+        }
+    }
+    let mut recorder = sink.map(|sink| Recorder::new(&device, config.label_prefix, size, sink));
+
+    let blitter = Blitter::new(
+        &device,
+        swap_chain_format,
+        config.blit_filter,
+        config.label_prefix,
+    );
+    let mut presenter = presenter(&device, &config, swap_chain_format);
+    let format = intermediate_format(&config, swap_chain_format);
+    let mut pass_targets = PassTargets::new(&device, &config, render_size, format, sample_count);
+    let mut intermediate = Intermediate::if_needed(
+        &device,
+        &blitter,
+        &config,
+        swap_chain_format,
+        render_size,
+        recorder.is_some(),
+    );
+    let mut frame_graph = if config.show_frame_graph {
+        Some(graph::FrameGraph::new(
+            &device,
+            swap_chain_format,
+            config.label_prefix,
+        ))
+    } else {
+        None
+    };
+    let mut spinner = if config.loading_indicator {
+        Some(spinner::Spinner::new(
+            &device,
+            swap_chain_format,
+            config.label_prefix,
+        ))
+    } else {
+        None
+    };
+    #[cfg(feature = "text")]
+    let mut text_renderer = text::TextRenderer::new(&device, format, config.label_prefix);
 
     // Initialize scene and GUI controls
-    let mut scene = S::new(&mut device, args);
+    let clipboard = config
+        .clipboard
+        .take()
+        .unwrap_or_else(|| Box::new(LocalClipboard::default()));
+    let mut post_draw = config.post_draw.take();
+    let mut on_swap_chain_rebuild = config.on_swap_chain_rebuild.take();
+    // Frame rate `RedrawPolicy::CappedToMonitor` caps to, updated as the window changes monitor
+    let mut monitor = window.current_monitor();
+    let mut monitor_fps = refresh_rate(&monitor);
+    let mut ctx = Context::new(
+        Some(window),
+        device.clone(),
+        queue.clone(),
+        clipboard,
+        sample_count,
+        format,
+    );
+    ctx.set_title(title);
+    ctx.set_paused(config.initial_paused);
+    ctx.set_label_prefix(config.label_prefix);
+    ctx.set_features(device.features());
+    ctx.set_seed(config.seed.unwrap_or_else(|| {
+        let seed = random_seed();
+        log::info!(
+            "Seed {}, set LaunchConfig::seed to reproduce this run",
+            seed
+        );
+        seed
+    }));
+    ctx.set_depth(
+        config.depth_buffer,
+        config.depth_prepass,
+        config.depth_clear,
+    );
+    ctx.set_swap_chain_format(swap_chain_format);
+    ctx.set_memory_stats(target_memory_stats(
+        size,
+        &config,
+        sample_count,
+        swap_chain_format,
+        intermediate.is_some(),
+        recorder.is_some(),
+    ));
+    let mut scene = S::new(&mut ctx, &device, args);
+    if config.warmup_frames > 0 {
+        warm_up(
+            &mut scene,
+            &ctx,
+            &device,
+            &queue,
+            &config,
+            render_size,
+            &pass_targets,
+        );
+    }
+    let mut step_requested = false;
+    let mut next_title_update = Instant::now();
+    let mut last_update = Instant::now();
+    let mut redraw_requested = true;
+    let mut next_draw = last_update;
+    let watchdog = config
+        .frame_timeout
+        .map(|timeout| Watchdog::new(timeout, config.error_handler.clone()));
+    assert!(
+        config.max_frames_in_flight != Some(0) && config.max_frame_latency != Some(0),
+        "Frame limits must be above zero"
+    );
+    let mut pacer = if config.max_frames_in_flight.is_some() || config.max_frame_latency.is_some() {
+        Some(pacing::FramePacer::new(&device, config.label_prefix))
+    } else {
+        None
+    };
+    let mut backed_up = false;
+
+    let mut on_exit = Some(on_exit);
 
     // Run event loop
-    event_loop.run(move |event, _, control_flow| {
+    Box::new(move |event, _, control_flow| {
+        scene.raw_event(&event);
         match event {
             Event::WindowEvent { event, .. } => {
-                scene.event(&event);
+                redraw_requested = true;
+                ctx.input_mut().handle_event(&event);
+                ctx.track_window(&event);
+                scene.event(&mut ctx, &event);
                 match event {
-                    WindowEvent::Resized(_) => {
+                    WindowEvent::Resized(size) => {
+                        ctx.set_hidden(size.width == 0 || size.height == 0);
                         resized = true;
+                        rebuild_reason.get_or_insert(SwapChainRebuildReason::Resized);
+                        if config.keep_frame_during_resize {
+                            resize_settles_at = Some(Instant::now() + RESIZE_SETTLE_TIME);
+                        }
+                    }
+                    WindowEvent::ScaleFactorChanged { .. } => {
+                        resized = true;
+                        rebuild_reason.get_or_insert(SwapChainRebuildReason::ScaleFactorChanged);
+                    }
+                    WindowEvent::ThemeChanged(theme) => {
+                        ctx.set_theme(theme);
+                    }
+                    WindowEvent::Moved(_) if config.redraw == RedrawPolicy::CappedToMonitor => {
+                        let current = ctx.expect_window().current_monitor();
+                        if current != monitor {
+                            monitor_fps = refresh_rate(&current);
+                            log::info!("Capping the frame rate to {} FPS", monitor_fps);
+                            monitor = current;
+                        }
+                    }
+                    WindowEvent::KeyboardInput { input, .. } => {
+                        if let (Some(keys), ElementState::Pressed, Some(key)) =
+                            (&config.debug_keys, input.state, input.virtual_keycode)
+                        {
+                            if key == keys.pause {
+                                ctx.set_paused(!ctx.is_paused());
+                            } else if key == keys.step && ctx.is_paused() {
+                                step_requested = true;
+                            }
+                        }
                     }
                     WindowEvent::CloseRequested => {
                         *control_flow = ControlFlow::Exit;
@@ -88,38 +788,933 @@ pub fn launch<S: 'static + Scene>(args: S::Args) {
                     _ => {}
                 }
             }
+            Event::Suspended => {
+                // The surface can't be presented to until the app resumes
+                suspended = true;
+                swap_chain = None;
+                scene.suspended(&mut ctx);
+            }
+            Event::Resumed => {
+                if suspended {
+                    surface = unsafe { instance.create_surface(ctx.expect_window()) };
+                    suspended = false;
+                    rebuild_reason.get_or_insert(SwapChainRebuildReason::Resumed);
+                }
+                scene.resumed(&mut ctx);
+            }
+            Event::DeviceEvent { device_id, event } => {
+                ctx.track_device(device_id, &event);
+                match event {
+                    DeviceEvent::Added => scene.device_added(&mut ctx, device_id),
+                    DeviceEvent::Removed => scene.device_removed(&mut ctx, device_id),
+                    _ => {}
+                }
+                scene.device_event(&mut ctx, device_id, &event);
+            }
             Event::MainEventsCleared => {
-                // Rebuild the swapchain if necessary
-                if resized {
-                    let size = window.inner_size();
-
-                    swap_chain = device.create_swap_chain(
-                        &surface,
-                        &wgpu::SwapChainDescriptor {
-                            usage: wgpu::TextureUsage::OUTPUT_ATTACHMENT,
-                            format: SWAPCHAIN_FORMAT,
-                            width: size.width,
-                            height: size.height,
-                            present_mode: wgpu::PresentMode::Mailbox,
-                        },
+                // Skip drawing while suspended, paused or hidden, if requested, or as the redraw
+                // policy dictates; a step requested through the debug keys draws while paused.
+                // A minimized window has no area, and zero-sized targets are invalid, so
+                // everything sized to it is left as it is until it's restored. Time spent
+                // skipped isn't counted toward the next frame.
+                let stepping = std::mem::take(&mut step_requested);
+                let size = ctx.expect_window().inner_size();
+                if suspended
+                    || ctx.is_paused() && !stepping
+                    || config.pause_when_hidden && ctx.is_hidden()
+                    || size.width == 0
+                    || size.height == 0
+                {
+                    last_update = Instant::now();
+                    return;
+                }
+
+                // Skip the frame while the GPU is too far behind, rather than add to the backlog
+                backed_up = match (pacer.as_mut(), config.max_frames_in_flight) {
+                    (Some(pacer), Some(limit)) => pacer.in_flight(&device) >= limit,
+                    _ => false,
+                };
+                if backed_up {
+                    return;
+                }
+                let now = Instant::now();
+                let draw = stepping
+                    || match config.redraw {
+                        RedrawPolicy::Always => true,
+                        RedrawPolicy::Reactive => {
+                            std::mem::take(&mut redraw_requested) || ctx.is_invalidated()
+                        }
+                        RedrawPolicy::Capped(_) | RedrawPolicy::CappedToMonitor => now >= next_draw,
+                    };
+                if !draw {
+                    if config.redraw == RedrawPolicy::Reactive {
+                        // Idle until something asks for a redraw
+                        last_update = now;
+                    }
+                    return;
+                }
+                let cap = match config.redraw {
+                    RedrawPolicy::Capped(fps) => Some(fps),
+                    RedrawPolicy::CappedToMonitor => Some(monitor_fps),
+                    RedrawPolicy::Always | RedrawPolicy::Reactive => None,
+                };
+                if let Some(fps) = cap {
+                    // Keep a steady cadence unless we've fallen more than a frame behind
+                    next_draw = (next_draw + Duration::from_secs(1) / fps).max(now);
+                }
+                let invalidated = ctx.take_invalidated();
+                if let Some(intermediate) = intermediate.as_mut() {
+                    intermediate.dirty |= invalidated;
+                }
+
+                // Create or rebuild the swapchain if necessary, or if the scene asked
+                match ctx.take_rebuild_request() {
+                    Some(SurfaceRebuild::Surface) => {
+                        swap_chain = None;
+                        surface = unsafe { instance.create_surface(ctx.expect_window()) };
+                        resized = true;
+                        rebuild_reason.get_or_insert(SwapChainRebuildReason::SurfaceLost);
+                    }
+                    Some(SurfaceRebuild::SwapChain) => {
+                        resized = true;
+                        rebuild_reason.get_or_insert(SwapChainRebuildReason::Requested);
+                    }
+                    None => {}
+                }
+                if let Some(reason) = rebuild_reason.take() {
+                    let mut desc = swap_chain_descriptor(
+                        &config,
+                        swap_chain_format,
+                        scene.surface_usage(),
+                        size,
+                    );
+                    scene.configure_surface(&mut desc);
+                    if desc.format != swap_chain_format {
+                        log::warn!(
+                            "Swapchain format can't be changed from {:?} to {:?} while running",
+                            swap_chain_format,
+                            desc.format
+                        );
+                        desc.format = swap_chain_format;
+                    }
+                    let (new_swap_chain, present_mode) =
+                        swap_chain::create_swap_chain(&device, &surface, &desc);
+                    if present_mode != desc.present_mode {
+                        log::warn!(
+                            "Present mode {:?} is not supported by the surface, fell back to {:?}",
+                            desc.present_mode,
+                            present_mode
+                        );
+                        // Skip straight to the supported mode on later rebuilds
+                        config.present_mode = present_mode;
+                    } else if swap_chain.is_none() {
+                        log::info!("Using present mode {:?}", present_mode);
+                    }
+                    if config.startup_banner && swap_chain.is_none() {
+                        log::info!(
+                            "wgpu_launchpad: {:?} on {}, {}x{}, {:?}, {:?}",
+                            adapter_info.backend,
+                            adapter_info.name,
+                            size.width,
+                            size.height,
+                            present_mode,
+                            swap_chain_format
+                        );
+                        config.startup_banner = false;
+                    }
+                    swap_chain = Some(new_swap_chain);
+                    if let Some(hook) = on_swap_chain_rebuild.as_mut() {
+                        hook(reason, size.width, size.height);
+                    }
+                }
+
+                // While the window is being resized, keep drawing at the old size and stretch
+                // the image over the new swapchain, until the size settles
+                let settling = resize_settles_at.is_some_and(|settles_at| now < settles_at);
+                if settling {
+                    resized = false;
+                } else {
+                    resize_settles_at = None;
+                }
+
+                // Rebuild everything else sized to the window or set by the render settings,
+                // and let the scene follow suit
+                let settings_changed = match ctx.take_settings_request() {
+                    Some(settings) => {
+                        config.supersample = settings.supersample;
+                        config.intermediate_format = settings.intermediate_format;
+                        if settings.anti_aliasing != config.anti_aliasing {
+                            config.anti_aliasing = settings.anti_aliasing;
+                            sample_count = configured_sample_count(&config);
+                            ctx.set_sample_count(sample_count);
+                            presenter = self::presenter(&device, &config, swap_chain_format);
+                        }
+                        true
+                    }
+                    None => false,
+                };
+                if resized || size != target_size && !settling || settings_changed {
+                    target_size = size;
+                    let render_size = scaled_size(size, config.supersample);
+                    let format = intermediate_format(&config, swap_chain_format);
+                    pass_targets =
+                        PassTargets::new(&device, &config, render_size, format, sample_count);
+                    intermediate = Intermediate::if_needed(
+                        &device,
+                        &blitter,
+                        &config,
+                        swap_chain_format,
+                        render_size,
+                        recorder.is_some(),
+                    );
+                    if let Some(recorder) = recorder.as_mut() {
+                        recorder.resize(&device, config.label_prefix, size);
+                    }
+                    ctx.set_format(format);
+                    #[cfg(feature = "text")]
+                    {
+                        text_renderer =
+                            text::TextRenderer::new(&device, format, config.label_prefix);
+                    }
+                    ctx.set_memory_stats(target_memory_stats(
+                        size,
+                        &config,
+                        sample_count,
+                        swap_chain_format,
+                        intermediate.is_some(),
+                        recorder.is_some(),
+                    ));
+                    scene.recreate(
+                        &device,
+                        format,
+                        render_size.width,
+                        render_size.height,
+                        sample_count,
                     );
 
                     resized = false;
                 }
 
-                // Get another frame
-                let frame = swap_chain.get_current_frame().expect("Next frame");
+                // Wait for the GPU to catch up before reading input for the next frame
+                if let (Some(pacer), Some(latency)) = (pacer.as_mut(), config.max_frame_latency) {
+                    pacer.wait(&device, latency);
+                }
+
+                // Advance the scene
+                let now = Instant::now();
+                if stepping {
+                    last_update = now - config.fixed_dt.unwrap_or(STEP_DT);
+                }
+                ctx.frame_stats_mut().push(now - last_update);
+                ctx.set_dt(
+                    config
+                        .fixed_dt
+                        .unwrap_or_else(|| (now - last_update).min(config.max_dt)),
+                );
+                last_update = now;
+                scene.update(&mut ctx);
+
+                // Submit compute work by itself, so it doesn't wait on the next frame, unless
+                // it's to go out with the frame's own work
+                let separate_compute = scene.has_compute();
+                let mut compute = if separate_compute {
+                    let mut compute_encoder =
+                        device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                            label: Some(&format!("{}Compute", config.label_prefix)),
+                        });
+                    scene.compute(&mut compute_encoder);
+                    Some(compute_encoder.finish())
+                } else {
+                    None
+                };
+                if !config.single_submit {
+                    submit_compute(&queue, &mut compute);
+                }
+
+                // Get another frame; acquisition is already bounded by the swapchain's own timeout
+                let swap_chain = swap_chain.as_mut().expect("Swapchain was just created");
+                let frame = match swap_chain.get_current_frame() {
+                    Ok(frame) => frame,
+                    Err(wgpu::SwapChainError::Timeout) => {
+                        log::warn!("Timed out acquiring the next frame, skipping it");
+                        ctx.frame_stats_mut().count_skipped();
+                        submit_compute(&queue, &mut compute);
+                        return;
+                    }
+                    Err(wgpu::SwapChainError::Outdated) | Err(wgpu::SwapChainError::Lost) => {
+                        ctx.frame_stats_mut().count_skipped();
+                        submit_compute(&queue, &mut compute);
+                        resized = true;
+                        rebuild_reason.get_or_insert(SwapChainRebuildReason::Outdated);
+                        return;
+                    }
+                    Err(err @ wgpu::SwapChainError::OutOfMemory) => {
+                        error::report(&config.error_handler, LaunchError::SwapChain(err));
+                        *control_flow = ControlFlow::Exit;
+                        return;
+                    }
+                };
+                if let Some(watchdog) = &watchdog {
+                    watchdog.begin(ctx.frame_index());
+                }
+                scene.frame_begin(&frame.output.view, ctx.frame_index());
 
-                let mut encoder =
-                    device.create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
+                let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                    label: Some(&format!("{}Frame", config.label_prefix)),
+                });
+                if !separate_compute {
+                    scene.compute(&mut encoder);
+                }
 
-                // Draw the scene
-                scene.draw(&mut encoder, &frame.output.view);
+                // Draw the scene, into the intermediate if there is one; a static image is only
+                // drawn again when it's dirty. Until it's ready, show the spinner instead.
+                let loading = !scene.is_ready() && spinner.is_some();
+                if let Some(spinner) = spinner.as_mut().filter(|_| loading) {
+                    spinner.draw(&ctx, &mut encoder, &frame.output.view, scene.clear_color());
+                } else {
+                    match intermediate.as_mut() {
+                        Some(intermediate) => {
+                            if !config.static_image || intermediate.dirty {
+                                render_scene(
+                                    &mut scene,
+                                    &mut encoder,
+                                    &intermediate.view,
+                                    &pass_targets,
+                                    &ctx,
+                                );
+                                #[cfg(feature = "text")]
+                                text_renderer.draw(
+                                    &device,
+                                    &queue,
+                                    &mut encoder,
+                                    &intermediate.view,
+                                    target_size,
+                                    ctx.text(),
+                                );
+                                intermediate.dirty = false;
+                            }
+                            let targets = std::iter::once(&frame.output.view)
+                                .chain(recorder.as_ref().map(Recorder::view));
+                            for target in targets {
+                                if !scene.present(&mut encoder, &intermediate.bind_group, target) {
+                                    presenter.as_ref().unwrap_or(&blitter).blit(
+                                        &mut encoder,
+                                        &intermediate.bind_group,
+                                        target,
+                                    );
+                                }
+                            }
+                        }
+                        None => {
+                            render_scene(
+                                &mut scene,
+                                &mut encoder,
+                                &frame.output.view,
+                                &pass_targets,
+                                &ctx,
+                            );
+                            #[cfg(feature = "text")]
+                            text_renderer.draw(
+                                &device,
+                                &queue,
+                                &mut encoder,
+                                &frame.output.view,
+                                size,
+                                ctx.text(),
+                            );
+                        }
+                    }
+                }
+                // Recordings get the same extra passes, such as a watermark
+                if let Some(post_draw) = post_draw.as_mut() {
+                    let targets = std::iter::once(&frame.output.view)
+                        .chain(recorder.as_ref().map(Recorder::view));
+                    for target in targets {
+                        post_draw(&mut encoder, target);
+                    }
+                }
+                if let Some(frame_graph) = frame_graph.as_mut() {
+                    frame_graph.draw(
+                        &device,
+                        &queue,
+                        &mut encoder,
+                        &frame.output.view,
+                        size,
+                        ctx.frame_stats(),
+                    );
+                }
 
                 // Then we submit the work
-                queue.submit(Some(encoder.finish()));
+                if let Some(pacer) = pacer.as_mut() {
+                    pacer.record(&device, &mut encoder);
+                }
+                queue.submit(compute.into_iter().chain(Some(encoder.finish())));
+                ctx.frame_stats_mut().count_submitted(frame.suboptimal);
+                if let Some(pacer) = pacer.as_mut() {
+                    pacer.submitted();
+                }
+                if let Some(watchdog) = &watchdog {
+                    device.poll(wgpu::Maintain::Wait);
+                    watchdog.end();
+                }
+                if let (Some(recorder), false) = (recorder.as_mut(), loading) {
+                    let saved =
+                        recorder.save(&device, &queue, config.label_prefix, ctx.frame_index());
+                    if let Err(err) = saved {
+                        error::report(&config.error_handler, LaunchError::Record(err));
+                    }
+                }
+                ctx.next_frame();
+                if config.show_fps_in_title && now >= next_title_update {
+                    next_title_update = now + FPS_TITLE_INTERVAL;
+                    let average = ctx.frame_stats().average().as_secs_f64();
+                    if average > 0.0 {
+                        ctx.set_title_fps(1.0 / average);
+                    }
+                }
+            }
+            // Wait for whatever should trigger the next frame
+            Event::RedrawEventsCleared if *control_flow != ControlFlow::Exit => {
+                scene.frame_end();
+                *control_flow = if suspended
+                    || ctx.is_paused()
+                    || config.pause_when_hidden && ctx.is_hidden()
+                {
+                    ControlFlow::Wait
+                } else if backed_up {
+                    // Check on the GPU again soon
+                    ControlFlow::Poll
+                } else if spinner.is_some() && !scene.is_ready() {
+                    // Keep the spinner turning
+                    ControlFlow::Poll
+                } else if let Some(settles_at) = resize_settles_at {
+                    // Draw again at the new size once it settles
+                    redraw_requested = true;
+                    ControlFlow::WaitUntil(settles_at)
+                } else {
+                    match config.redraw {
+                        RedrawPolicy::Always => ControlFlow::Poll,
+                        RedrawPolicy::Reactive if ctx.is_invalidated() => ControlFlow::Poll,
+                        RedrawPolicy::Reactive => ControlFlow::Wait,
+                        RedrawPolicy::Capped(_) | RedrawPolicy::CappedToMonitor => {
+                            ControlFlow::WaitUntil(next_draw)
+                        }
+                    }
+                };
+            }
+            Event::LoopDestroyed => {
+                if let Some(on_exit) = on_exit.take() {
+                    on_exit(&scene);
+                }
+                // Finish the video, if recording one
+                drop(recorder.take());
             }
             _ => {}
         }
     })
 }
+
+/// Submit the compute work held back for the frame, if there is any
+fn submit_compute(queue: &wgpu::Queue, compute: &mut Option<wgpu::CommandBuffer>) {
+    if let Some(compute) = compute.take() {
+        queue.submit(Some(compute));
+    }
+}
+
+/// Blitter replacing the plain blit onto the swapchain when the image needs processing on the
+/// way, for `LaunchConfig::hdr` or FXAA
+fn presenter(
+    device: &wgpu::Device,
+    config: &LaunchConfig,
+    swap_chain_format: wgpu::TextureFormat,
+) -> Option<Blitter> {
+    if config.hdr {
+        if config.anti_aliasing == AntiAliasing::Fxaa {
+            log::warn!("FXAA isn't applied on top of HDR tone mapping");
+        }
+        Some(Blitter::tone_mapper(
+            device,
+            swap_chain_format,
+            config.blit_filter,
+            config.label_prefix,
+            config.tone_mapping,
+        ))
+    } else if config.anti_aliasing == AntiAliasing::Fxaa {
+        Some(Blitter::fxaa(
+            device,
+            swap_chain_format,
+            config.label_prefix,
+        ))
+    } else {
+        None
+    }
+}
+
+/// Panic naming the features the scene requires that the adapter lacks, or with `gl_compat`
+/// only warn and continue without them
+pub(crate) fn check_required_features<S: Scene>(
+    available: wgpu::Features,
+    adapter_name: &str,
+    compat: bool,
+) {
+    let missing = S::required_features() - available;
+    if missing.is_empty() {
+        return;
+    }
+    if compat {
+        log::warn!(
+            "Adapter \"{}\" lacks required features {:?}, continuing without them",
+            adapter_name,
+            missing
+        );
+    } else {
+        panic!(
+            "Adapter \"{}\" does not support the features required by the scene: {:?}",
+            adapter_name, missing
+        );
+    }
+}
+
+/// Default limits, lowered to what the adapter supports, for `LaunchConfig::gl_compat`
+fn compat_limits(adapter: wgpu::Limits) -> wgpu::Limits {
+    let default = wgpu::Limits::default();
+    let limits = wgpu::Limits {
+        max_bind_groups: default.max_bind_groups.min(adapter.max_bind_groups),
+        max_dynamic_uniform_buffers_per_pipeline_layout: default
+            .max_dynamic_uniform_buffers_per_pipeline_layout
+            .min(adapter.max_dynamic_uniform_buffers_per_pipeline_layout),
+        max_dynamic_storage_buffers_per_pipeline_layout: default
+            .max_dynamic_storage_buffers_per_pipeline_layout
+            .min(adapter.max_dynamic_storage_buffers_per_pipeline_layout),
+        max_sampled_textures_per_shader_stage: default
+            .max_sampled_textures_per_shader_stage
+            .min(adapter.max_sampled_textures_per_shader_stage),
+        max_samplers_per_shader_stage: default
+            .max_samplers_per_shader_stage
+            .min(adapter.max_samplers_per_shader_stage),
+        max_storage_buffers_per_shader_stage: default
+            .max_storage_buffers_per_shader_stage
+            .min(adapter.max_storage_buffers_per_shader_stage),
+        max_storage_textures_per_shader_stage: default
+            .max_storage_textures_per_shader_stage
+            .min(adapter.max_storage_textures_per_shader_stage),
+        max_uniform_buffers_per_shader_stage: default
+            .max_uniform_buffers_per_shader_stage
+            .min(adapter.max_uniform_buffers_per_shader_stage),
+        max_uniform_buffer_binding_size: default
+            .max_uniform_buffer_binding_size
+            .min(adapter.max_uniform_buffer_binding_size),
+        max_push_constant_size: default
+            .max_push_constant_size
+            .min(adapter.max_push_constant_size),
+    };
+    if limits != default {
+        log::warn!("Lowering device limits to {:?}", limits);
+    }
+    limits
+}
+
+/// Describe the adapter and the device negotiated with it
+fn log_device_info(info: &wgpu::AdapterInfo, device: &wgpu::Device) {
+    log::info!(
+        "Adapter \"{}\" ({:?}, {:?} backend, vendor {:#06x}, device {:#06x})",
+        info.name,
+        info.device_type,
+        info.backend,
+        info.vendor,
+        info.device
+    );
+    log::info!("Enabled features: {:?}", device.features());
+    log::info!("Device limits: {:?}", device.limits());
+}
+
+/// Move the window to the middle of the primary monitor
+fn center_window(window: &winit::window::Window) {
+    let monitor = window.primary_monitor();
+    let (monitor_pos, monitor_size) = (monitor.position(), monitor.size());
+    let window_size = window.outer_size();
+    window.set_outer_position(winit::dpi::PhysicalPosition::new(
+        monitor_pos.x + (monitor_size.width as i32 - window_size.width as i32) / 2,
+        monitor_pos.y + (monitor_size.height as i32 - window_size.height as i32) / 2,
+    ));
+}
+
+/// Pick the preferred swapchain format the surface supports. Recordings are read back as 8-bit
+/// sRGB, so they stick to the default format.
+fn choose_swap_chain_format(config: &LaunchConfig) -> wgpu::TextureFormat {
+    let preferred = &config.swap_chain_formats;
+    if config.records() || *preferred == [SWAPCHAIN_FORMAT] {
+        return SWAPCHAIN_FORMAT;
+    }
+    swap_chain::select_format(preferred).unwrap_or_else(|| {
+        log::warn!(
+            "None of the swapchain formats {:?} are known to be supported, using {:?}",
+            preferred,
+            SWAPCHAIN_FORMAT
+        );
+        SWAPCHAIN_FORMAT
+    })
+}
+
+fn swap_chain_descriptor(
+    config: &LaunchConfig,
+    format: wgpu::TextureFormat,
+    usage: wgpu::TextureUsage,
+    size: winit::dpi::PhysicalSize<u32>,
+) -> wgpu::SwapChainDescriptor {
+    wgpu::SwapChainDescriptor {
+        usage: usage | wgpu::TextureUsage::OUTPUT_ATTACHMENT,
+        format,
+        width: size.width,
+        height: size.height,
+        present_mode: config.present_mode,
+    }
+}
+
+/// Record the depth prepass if there is one, the managed pass, then the scene's own drawing
+fn render_scene<S: Scene>(
+    scene: &mut S,
+    encoder: &mut wgpu::CommandEncoder,
+    target: &wgpu::TextureView,
+    targets: &PassTargets,
+    ctx: &Context,
+) {
+    // With MSAA the scene draws into the multisampled attachment, resolved into `target` at the
+    // end
+    let depth_view = targets.depth.as_ref();
+    let color_view = targets.multisampled.as_ref().unwrap_or(target);
+
+    // Depth is cleared by the first pass of the frame which uses it, and kept after that
+    let mut depth_load = wgpu::LoadOp::Clear(ctx.depth_clear());
+    if let Some(attachment) = depth_view.filter(|_| ctx.depth_prepass()) {
+        let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            color_attachments: &[],
+            depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachmentDescriptor {
+                attachment,
+                depth_ops: Some(wgpu::Operations {
+                    load: depth_load,
+                    store: true,
+                }),
+                stencil_ops: None,
+            }),
+        });
+        scene.depth_prepass(&mut pass);
+        depth_load = wgpu::LoadOp::Load;
+    }
+    {
+        let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            color_attachments: &[wgpu::RenderPassColorAttachmentDescriptor {
+                attachment: color_view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(scene.clear_color()),
+                    store: true,
+                },
+            }],
+            depth_stencil_attachment: depth_view.map(|attachment| {
+                wgpu::RenderPassDepthStencilAttachmentDescriptor {
+                    attachment,
+                    depth_ops: Some(wgpu::Operations {
+                        load: depth_load,
+                        store: true,
+                    }),
+                    stencil_ops: None,
+                }
+            }),
+        });
+        scene.draw_pass(&mut pass);
+    }
+    scene.draw(encoder, color_view);
+
+    // An empty pass is enough to resolve the samples
+    if let Some(multisampled) = &targets.multisampled {
+        encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            color_attachments: &[wgpu::RenderPassColorAttachmentDescriptor {
+                attachment: multisampled,
+                resolve_target: Some(target),
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Load,
+                    store: false,
+                },
+            }],
+            depth_stencil_attachment: None,
+        });
+    }
+}
+
+/// Draw `LaunchConfig::warmup_frames` throwaway frames offscreen and wait for them to finish, so
+/// that the first presented frame doesn't stall on pipeline compilation or uploads
+fn warm_up<S: Scene>(
+    scene: &mut S,
+    ctx: &Context,
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    config: &LaunchConfig,
+    size: winit::dpi::PhysicalSize<u32>,
+    targets: &PassTargets,
+) {
+    let label = format!("{}Warmup", config.label_prefix);
+    let target = target::create_view(
+        device,
+        &label,
+        size,
+        ctx.format(),
+        wgpu::TextureUsage::OUTPUT_ATTACHMENT,
+    );
+    for _ in 0..config.warmup_frames {
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some(&label),
+        });
+        render_scene(scene, &mut encoder, &target, targets, ctx);
+        queue.submit(Some(encoder.finish()));
+    }
+    device.poll(wgpu::Maintain::Wait);
+}
+
+/// Seed differing between runs, from the standard library's randomly keyed hasher and the time
+fn random_seed() -> u64 {
+    use std::hash::BuildHasher;
+    std::collections::hash_map::RandomState::new().hash_one(std::time::SystemTime::now())
+}
+
+/// Samples per pixel of the managed pass, set by `LaunchConfig::anti_aliasing`
+fn configured_sample_count(config: &LaunchConfig) -> u32 {
+    match config.anti_aliasing {
+        AntiAliasing::Msaa(samples) => {
+            supported_sample_count(samples.into(), config.max_sample_count)
+        }
+        AntiAliasing::None | AntiAliasing::Fxaa => 1,
+    }
+}
+
+/// Largest power of two no greater than `requested` or `max`, warning if it's lower than requested
+pub(crate) fn supported_sample_count(requested: u32, max: u32) -> u32 {
+    let limit = requested.min(max).max(1);
+    let supported = 1 << (31 - limit.leading_zeros());
+    if supported != requested {
+        log::warn!(
+            "Sample count {} isn't supported, using {}",
+            requested,
+            supported
+        );
+    }
+    supported
+}
+
+/// Attachments of the managed pass besides the target it renders to
+pub(crate) struct PassTargets {
+    /// Depth attachment, unless `LaunchConfig::depth_buffer` is off
+    pub depth: Option<wgpu::TextureView>,
+    /// Color attachment the scene draws into with MSAA, resolved into the target
+    pub multisampled: Option<wgpu::TextureView>,
+}
+
+impl PassTargets {
+    /// Create the attachments the configuration asks for, for a target of this size, format and
+    /// sample count
+    fn new(
+        device: &wgpu::Device,
+        config: &LaunchConfig,
+        size: winit::dpi::PhysicalSize<u32>,
+        format: wgpu::TextureFormat,
+        sample_count: u32,
+    ) -> Self {
+        let depth = if config.depth_buffer {
+            Some(create_depth_view(
+                device,
+                config.label_prefix,
+                size,
+                sample_count,
+            ))
+        } else {
+            None
+        };
+        let multisampled = if sample_count > 1 {
+            let texture = target::create_texture(
+                device,
+                &format!("{}Multisampled frame", config.label_prefix),
+                size,
+                sample_count,
+                format,
+                wgpu::TextureUsage::OUTPUT_ATTACHMENT,
+            );
+            Some(texture.create_view(&wgpu::TextureViewDescriptor::default()))
+        } else {
+            None
+        };
+        Self {
+            depth,
+            multisampled,
+        }
+    }
+}
+
+/// Memory held by the render targets for a window of the given size
+fn target_memory_stats(
+    size: winit::dpi::PhysicalSize<u32>,
+    config: &LaunchConfig,
+    sample_count: u32,
+    swap_chain_format: wgpu::TextureFormat,
+    intermediate: bool,
+    recording: bool,
+) -> MemoryStats {
+    let render_size = scaled_size(size, config.supersample);
+    let format = if intermediate {
+        intermediate_format(config, swap_chain_format)
+    } else {
+        swap_chain_format
+    };
+    let mut stats = MemoryStats::default();
+    if config.depth_buffer {
+        stats.add_texture(render_size, DEPTH_FORMAT, sample_count);
+    }
+    if sample_count > 1 {
+        stats.add_texture(render_size, format, sample_count);
+    }
+    if intermediate {
+        stats.add_texture(render_size, format, 1);
+    }
+    if recording {
+        stats.add_texture(size, SWAPCHAIN_FORMAT, 1);
+    }
+    stats
+}
+
+/// Fastest refresh rate `monitor` offers at its current resolution
+fn refresh_rate(monitor: &winit::monitor::MonitorHandle) -> u32 {
+    let size = monitor.size();
+    monitor
+        .video_modes()
+        .filter(|mode| mode.size() == size)
+        .map(|mode| u32::from(mode.refresh_rate()))
+        .max()
+        .filter(|&rate| rate > 0)
+        .unwrap_or(FALLBACK_REFRESH_RATE)
+}
+
+/// Panic on supersample factors which would shrink the render target
+pub(crate) fn assert_supersample(supersample: f32) {
+    assert!(
+        supersample >= 1.0,
+        "Supersample factor must be at least 1.0, got {}",
+        supersample
+    );
+}
+
+/// Scale a window size by the supersample factor
+fn scaled_size(size: winit::dpi::PhysicalSize<u32>, factor: f32) -> winit::dpi::PhysicalSize<u32> {
+    winit::dpi::PhysicalSize::new(
+        (size.width as f32 * factor).round() as u32,
+        (size.height as f32 * factor).round() as u32,
+    )
+}
+
+/// Format the scene renders into when drawing to an intermediate
+fn intermediate_format(
+    config: &LaunchConfig,
+    swap_chain_format: wgpu::TextureFormat,
+) -> wgpu::TextureFormat {
+    if config.hdr {
+        HDR_FORMAT
+    } else {
+        config.intermediate_format.unwrap_or(swap_chain_format)
+    }
+}
+
+/// Offscreen image of the scene, presented to the swapchain; kept between frames for
+/// `LaunchConfig::static_image`, larger than the window for `LaunchConfig::supersample` and of
+/// `LaunchConfig::intermediate_format` if one is set
+struct Intermediate {
+    view: wgpu::TextureView,
+    bind_group: wgpu::BindGroup,
+    /// Whether the scene must be rendered again before the next blit
+    dirty: bool,
+}
+
+impl Intermediate {
+    /// Create the intermediate if the configuration calls for rendering to one
+    fn if_needed(
+        device: &wgpu::Device,
+        blitter: &Blitter,
+        config: &LaunchConfig,
+        swap_chain_format: wgpu::TextureFormat,
+        size: winit::dpi::PhysicalSize<u32>,
+        recording: bool,
+    ) -> Option<Self> {
+        let needed = config.static_image
+            || config.keep_frame_during_resize
+            || config.supersample > 1.0
+            || config.intermediate_format.is_some()
+            || config.hdr
+            || config.anti_aliasing == AntiAliasing::Fxaa
+            || recording;
+        let format = intermediate_format(config, swap_chain_format);
+        if needed {
+            Some(Self::new(
+                device,
+                blitter,
+                config.label_prefix,
+                size,
+                format,
+            ))
+        } else {
+            None
+        }
+    }
+
+    fn new(
+        device: &wgpu::Device,
+        blitter: &Blitter,
+        label_prefix: &str,
+        size: winit::dpi::PhysicalSize<u32>,
+        format: wgpu::TextureFormat,
+    ) -> Self {
+        let view = target::create_view(
+            device,
+            &format!("{}Intermediate frame", label_prefix),
+            size,
+            format,
+            wgpu::TextureUsage::OUTPUT_ATTACHMENT | wgpu::TextureUsage::SAMPLED,
+        );
+        let bind_group = blitter.bind_group(device, &view);
+        Self {
+            view,
+            bind_group,
+            dirty: true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use winit::dpi::PhysicalSize;
+
+    #[test]
+    fn sample_count_rounds_down_to_a_power_of_two() {
+        assert_eq!(supported_sample_count(0, 4), 1);
+        assert_eq!(supported_sample_count(1, 4), 1);
+        assert_eq!(supported_sample_count(3, 4), 2);
+        assert_eq!(supported_sample_count(4, 4), 4);
+        assert_eq!(supported_sample_count(255, 4), 4);
+    }
+
+    #[test]
+    fn sample_count_is_clamped_to_the_max() {
+        assert_eq!(supported_sample_count(8, 4), 4);
+        assert_eq!(supported_sample_count(16, 1), 1);
+        assert_eq!(supported_sample_count(8, 6), 4);
+    }
+
+    #[test]
+    fn scaled_size_rounds_to_the_nearest_pixel() {
+        let size = PhysicalSize::new(101, 33);
+        assert_eq!(scaled_size(size, 1.0), size);
+        assert_eq!(scaled_size(size, 0.5), PhysicalSize::new(51, 17));
+        assert_eq!(scaled_size(size, 2.0), PhysicalSize::new(202, 66));
+        assert_eq!(
+            scaled_size(PhysicalSize::new(0, 0), 2.0),
+            PhysicalSize::new(0, 0)
+        );
+    }
+}
@@ -0,0 +1,60 @@
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::sync::Arc;
+use std::thread;
+
+/// Builds a scene's heavy resources, such as its pipelines, on a background thread so that
+/// shader compilation doesn't stall the window. Start it from `Scene::new` with
+/// `Context::device`, call `poll` from `Scene::update`, and return `is_ready` from
+/// `Scene::is_ready` so that `LaunchConfig::loading_indicator` shows a spinner meanwhile.
+pub struct Loader<T> {
+    pending: Option<Receiver<T>>,
+    value: Option<T>,
+}
+
+impl<T: Send + 'static> Loader<T> {
+    /// Call `build` with the device on a new thread
+    pub fn spawn(
+        device: Arc<wgpu::Device>,
+        build: impl FnOnce(&wgpu::Device) -> T + Send + 'static,
+    ) -> Self {
+        let (sender, receiver) = mpsc::sync_channel(1);
+        thread::Builder::new()
+            .name("wgpu_launchpad loader".into())
+            .spawn(move || {
+                // The receiver is gone if the scene was dropped before loading finished
+                let _ = sender.send(build(&device));
+            })
+            .expect("Spawn loader thread");
+        Self {
+            pending: Some(receiver),
+            value: None,
+        }
+    }
+}
+
+impl<T> Loader<T> {
+    /// Take the result if the thread has finished, returning it once it's been swapped in
+    pub fn poll(&mut self) -> Option<&mut T> {
+        if let Some(receiver) = &self.pending {
+            match receiver.try_recv() {
+                Ok(value) => {
+                    self.value = Some(value);
+                    self.pending = None;
+                }
+                Err(TryRecvError::Empty) => {}
+                Err(TryRecvError::Disconnected) => panic!("Loader thread panicked"),
+            }
+        }
+        self.value.as_mut()
+    }
+
+    /// The result, once `poll` has swapped it in
+    pub fn get(&self) -> Option<&T> {
+        self.value.as_ref()
+    }
+
+    /// Whether `poll` has swapped the result in
+    pub fn is_ready(&self) -> bool {
+        self.value.is_some()
+    }
+}
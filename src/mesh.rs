@@ -0,0 +1,204 @@
+use crate::instances::{InstanceBuffer, Pod};
+use std::convert::TryInto;
+use wgpu::util::DeviceExt;
+
+/// Vertex and index buffers uploaded together, drawn with one indexed draw call. Meshes made
+/// with `with_edges` also upload the edges of their triangles, as line list indices for
+/// `draw_as`.
+pub struct Mesh {
+    vertices: wgpu::Buffer,
+    indices: wgpu::Buffer,
+    edges: Option<wgpu::Buffer>,
+    index_count: u32,
+    index_format: wgpu::IndexFormat,
+}
+
+impl Mesh {
+    /// Upload a mesh with 16-bit indices. `vertices` holds the raw bytes of the vertex data, as
+    /// laid out by the pipeline's vertex buffer descriptor.
+    pub fn new(device: &wgpu::Device, label: &str, vertices: &[u8], indices: &[u16]) -> Self {
+        let bytes: Vec<u8> = indices.iter().flat_map(|i| i.to_ne_bytes()).collect();
+        Self::from_bytes(
+            device,
+            label,
+            vertices,
+            &bytes,
+            None,
+            indices.len(),
+            wgpu::IndexFormat::Uint16,
+        )
+    }
+
+    /// Upload a mesh with 32-bit indices, for meshes with more than 65536 vertices
+    pub fn new_u32(device: &wgpu::Device, label: &str, vertices: &[u8], indices: &[u32]) -> Self {
+        let bytes: Vec<u8> = indices.iter().flat_map(|i| i.to_ne_bytes()).collect();
+        Self::from_bytes(
+            device,
+            label,
+            vertices,
+            &bytes,
+            None,
+            indices.len(),
+            wgpu::IndexFormat::Uint32,
+        )
+    }
+
+    /// Like `new`, also uploading the edges of the triangles so that `draw_as` can draw them as
+    /// a wireframe; they take twice the memory of the indices
+    pub fn with_edges(
+        device: &wgpu::Device,
+        label: &str,
+        vertices: &[u8],
+        indices: &[u16],
+    ) -> Self {
+        let bytes: Vec<u8> = indices.iter().flat_map(|i| i.to_ne_bytes()).collect();
+        let edges: Vec<u8> = edges(indices).flat_map(|i| i.to_ne_bytes()).collect();
+        Self::from_bytes(
+            device,
+            label,
+            vertices,
+            &bytes,
+            Some(&edges),
+            indices.len(),
+            wgpu::IndexFormat::Uint16,
+        )
+    }
+
+    /// Like `with_edges`, with 32-bit indices
+    pub fn with_edges_u32(
+        device: &wgpu::Device,
+        label: &str,
+        vertices: &[u8],
+        indices: &[u32],
+    ) -> Self {
+        let bytes: Vec<u8> = indices.iter().flat_map(|i| i.to_ne_bytes()).collect();
+        let edges: Vec<u8> = edges(indices).flat_map(|i| i.to_ne_bytes()).collect();
+        Self::from_bytes(
+            device,
+            label,
+            vertices,
+            &bytes,
+            Some(&edges),
+            indices.len(),
+            wgpu::IndexFormat::Uint32,
+        )
+    }
+
+    fn from_bytes(
+        device: &wgpu::Device,
+        label: &str,
+        vertices: &[u8],
+        indices: &[u8],
+        edges: Option<&[u8]>,
+        index_count: usize,
+        index_format: wgpu::IndexFormat,
+    ) -> Self {
+        let vertices = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some(&format!("{} vertices", label)),
+            contents: vertices,
+            usage: wgpu::BufferUsage::VERTEX,
+        });
+        let indices = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some(&format!("{} indices", label)),
+            contents: indices,
+            usage: wgpu::BufferUsage::INDEX,
+        });
+        let edges = edges.map(|edges| {
+            device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: Some(&format!("{} edges", label)),
+                contents: edges,
+                usage: wgpu::BufferUsage::INDEX,
+            })
+        });
+        Self {
+            vertices,
+            indices,
+            edges,
+            index_count: index_count.try_into().expect("Too many indices"),
+            index_format,
+        }
+    }
+
+    /// Format of the indices; pipelines drawing the mesh must use it as their `index_format`
+    pub fn index_format(&self) -> wgpu::IndexFormat {
+        self.index_format
+    }
+
+    /// Number of indices drawn
+    pub fn index_count(&self) -> u32 {
+        self.index_count
+    }
+
+    /// Bind the buffers to vertex buffer slot 0 and draw every index, with the pipeline and bind
+    /// groups already set
+    pub fn draw<'a>(&'a self, pass: &mut wgpu::RenderPass<'a>) {
+        self.draw_instanced(pass, 0..1);
+    }
+
+    /// Like `draw`, for a range of instances
+    pub fn draw_instanced<'a>(
+        &'a self,
+        pass: &mut wgpu::RenderPass<'a>,
+        instances: std::ops::Range<u32>,
+    ) {
+        pass.set_vertex_buffer(0, self.vertices.slice(..));
+        pass.set_index_buffer(self.indices.slice(..));
+        pass.draw_indexed(0..self.index_count, 0, instances);
+    }
+
+    /// Like `draw`, or with `wireframe` draw the edges of its triangles as a line list, for the
+    /// line variant of a `WireframePipeline`. Drawing a wireframe requires a mesh made with
+    /// `with_edges`.
+    pub fn draw_as<'a>(&'a self, pass: &mut wgpu::RenderPass<'a>, wireframe: bool) {
+        if wireframe {
+            let edges = self
+                .edges
+                .as_ref()
+                .expect("Mesh has no edges to draw as a wireframe, create it with with_edges");
+            pass.set_vertex_buffer(0, self.vertices.slice(..));
+            pass.set_index_buffer(edges.slice(..));
+            pass.draw_indexed(0..self.index_count / 3 * 6, 0, 0..1);
+        } else {
+            self.draw(pass);
+        }
+    }
+
+    /// Like `draw`, once for every instance in `instances`, which is bound to vertex buffer
+    /// slot 1
+    pub fn draw_instances<'a, T: Pod>(
+        &'a self,
+        pass: &mut wgpu::RenderPass<'a>,
+        instances: &'a InstanceBuffer<T>,
+    ) {
+        if instances.is_empty() {
+            return;
+        }
+        pass.set_vertex_buffer(1, instances.slice());
+        self.draw_instanced(pass, 0..instances.len());
+    }
+}
+
+/// Every edge of every triangle, as pairs of indices; shared edges are repeated
+fn edges<T: Copy>(indices: &[T]) -> impl Iterator<Item = T> + '_ {
+    indices
+        .chunks_exact(3)
+        .flat_map(|t| IntoIterator::into_iter([t[0], t[1], t[1], t[2], t[2], t[0]]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn edges_of_each_triangle() {
+        let pairs: Vec<u16> = edges(&[0, 1, 2, 2, 1, 3]).collect();
+        assert_eq!(pairs, [0, 1, 1, 2, 2, 0, 2, 1, 1, 3, 3, 2]);
+    }
+
+    #[test]
+    fn edges_ignore_an_incomplete_triangle() {
+        assert_eq!(edges::<u32>(&[]).count(), 0);
+        assert_eq!(edges(&[0_u32, 1]).count(), 0);
+        assert_eq!(edges(&[0_u32, 1, 2, 3]).count(), 6);
+    }
+}
@@ -0,0 +1,278 @@
+use crate::WindowEvent;
+use std::f32::consts::FRAC_PI_2;
+use winit::dpi::PhysicalPosition;
+use winit::event::{ElementState, MouseButton, MouseScrollDelta};
+
+/// Column-major 4x4 matrix, as uploaded to a GLSL `mat4`
+pub type Matrix4 = [[f32; 4]; 4];
+
+/// Furthest the camera pitches up or down, just short of straight up so the view stays defined
+const MAX_PITCH: f32 = FRAC_PI_2 - 0.01;
+
+/// Pixels of `PixelDelta` scrolling, as sent by touchpads, treated as one line
+const PIXELS_PER_LINE: f32 = 20.0;
+
+/// Camera orbiting a target point: drag with the left mouse button to rotate, the middle button
+/// to pan, and scroll to zoom. Pass it every window event from `Scene::event`, then upload
+/// `view_projection` each frame.
+///
+/// Matrices are right-handed, looking down -Z in view space, and map depth to wgpu's 0 to 1.
+#[derive(Debug, Clone)]
+pub struct OrbitController {
+    /// Point the camera orbits and looks at
+    pub target: [f32; 3],
+    /// Distance from the target
+    pub distance: f32,
+    /// Rotation around the Y axis, in radians
+    pub yaw: f32,
+    /// Elevation above the target's horizon, in radians
+    pub pitch: f32,
+    /// Vertical field of view, in radians
+    pub fov_y: f32,
+    pub near: f32,
+    pub far: f32,
+    /// Radians rotated per pixel dragged
+    pub rotate_speed: f32,
+    /// Fraction of the distance panned per pixel dragged
+    pub pan_speed: f32,
+    /// Fraction of the distance zoomed per line scrolled
+    pub zoom_speed: f32,
+    aspect: f32,
+    cursor: Option<PhysicalPosition<f64>>,
+    rotating: bool,
+    panning: bool,
+}
+
+impl Default for OrbitController {
+    fn default() -> Self {
+        Self {
+            target: [0.0; 3],
+            distance: 5.0,
+            yaw: 0.0,
+            pitch: 0.3,
+            fov_y: 60_f32.to_radians(),
+            near: 0.1,
+            far: 1000.0,
+            rotate_speed: 0.01,
+            pan_speed: 0.002,
+            zoom_speed: 0.1,
+            aspect: 1.0,
+            cursor: None,
+            rotating: false,
+            panning: false,
+        }
+    }
+}
+
+impl OrbitController {
+    /// Set the width / height ratio of the target; `Resized` events do this automatically
+    pub fn set_aspect(&mut self, aspect: f32) {
+        self.aspect = aspect;
+    }
+
+    /// Track the mouse, and the window's aspect ratio
+    pub fn handle_event(&mut self, event: &WindowEvent) {
+        match event {
+            WindowEvent::Resized(size) if size.width > 0 && size.height > 0 => {
+                self.aspect = size.width as f32 / size.height as f32;
+            }
+            WindowEvent::MouseInput { state, button, .. } => {
+                let pressed = *state == ElementState::Pressed;
+                match button {
+                    MouseButton::Left => self.rotating = pressed,
+                    MouseButton::Middle => self.panning = pressed,
+                    _ => {}
+                }
+            }
+            WindowEvent::CursorMoved { position, .. } => {
+                if let Some(last) = self.cursor {
+                    let dx = (position.x - last.x) as f32;
+                    let dy = (position.y - last.y) as f32;
+                    if self.rotating {
+                        self.yaw -= dx * self.rotate_speed;
+                        self.pitch =
+                            (self.pitch + dy * self.rotate_speed).clamp(-MAX_PITCH, MAX_PITCH);
+                    }
+                    if self.panning {
+                        let (right, up, _) = self.basis();
+                        let scale = self.distance * self.pan_speed;
+                        for i in 0..3 {
+                            self.target[i] += (up[i] * dy - right[i] * dx) * scale;
+                        }
+                    }
+                }
+                self.cursor = Some(*position);
+            }
+            WindowEvent::CursorLeft { .. } => self.cursor = None,
+            WindowEvent::MouseWheel { delta, .. } => {
+                let lines = match delta {
+                    MouseScrollDelta::LineDelta(_, y) => *y,
+                    MouseScrollDelta::PixelDelta(position) => position.y as f32 / PIXELS_PER_LINE,
+                };
+                self.distance *= (1.0 - self.zoom_speed).powf(lines);
+            }
+            _ => {}
+        }
+    }
+
+    /// Position of the camera
+    pub fn eye(&self) -> [f32; 3] {
+        let (sin_yaw, cos_yaw) = self.yaw.sin_cos();
+        let (sin_pitch, cos_pitch) = self.pitch.sin_cos();
+        [
+            self.target[0] + self.distance * cos_pitch * sin_yaw,
+            self.target[1] + self.distance * sin_pitch,
+            self.target[2] + self.distance * cos_pitch * cos_yaw,
+        ]
+    }
+
+    /// World to view space transform
+    pub fn view(&self) -> Matrix4 {
+        let eye = self.eye();
+        let (s, u, f) = self.basis();
+        [
+            [s[0], u[0], -f[0], 0.0],
+            [s[1], u[1], -f[1], 0.0],
+            [s[2], u[2], -f[2], 0.0],
+            [-dot(s, eye), -dot(u, eye), dot(f, eye), 1.0],
+        ]
+    }
+
+    /// View to clip space transform
+    pub fn projection(&self) -> Matrix4 {
+        let h = 1.0 / (self.fov_y / 2.0).tan();
+        let depth = self.far / (self.near - self.far);
+        [
+            [h / self.aspect, 0.0, 0.0, 0.0],
+            [0.0, h, 0.0, 0.0],
+            [0.0, 0.0, depth, -1.0],
+            [0.0, 0.0, self.near * depth, 0.0],
+        ]
+    }
+
+    /// World to clip space transform, `projection() * view()`
+    pub fn view_projection(&self) -> Matrix4 {
+        let (a, b) = (self.projection(), self.view());
+        let mut product = [[0.0; 4]; 4];
+        for (col, column) in product.iter_mut().enumerate() {
+            for (row, value) in column.iter_mut().enumerate() {
+                *value = (0..4).map(|k| a[k][row] * b[col][k]).sum();
+            }
+        }
+        product
+    }
+
+    /// Camera right, up and forward directions
+    fn basis(&self) -> ([f32; 3], [f32; 3], [f32; 3]) {
+        let eye = self.eye();
+        let forward = normalize([
+            self.target[0] - eye[0],
+            self.target[1] - eye[1],
+            self.target[2] - eye[2],
+        ]);
+        let right = normalize(cross(forward, [0.0, 1.0, 0.0]));
+        let up = cross(right, forward);
+        (right, up, forward)
+    }
+}
+
+fn dot(a: [f32; 3], b: [f32; 3]) -> f32 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+fn cross(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+fn normalize(v: [f32; 3]) -> [f32; 3] {
+    let length = dot(v, v).sqrt();
+    [v[0] / length, v[1] / length, v[2] / length]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(a: f32, b: f32) {
+        assert!((a - b).abs() < 1e-4, "{} != {}", a, b);
+    }
+
+    /// Transform a point by a column-major matrix, dividing by w
+    fn transform(m: &Matrix4, p: [f32; 3]) -> [f32; 3] {
+        let v = [p[0], p[1], p[2], 1.0];
+        let mut out = [0.0; 4];
+        for (row, value) in out.iter_mut().enumerate() {
+            *value = (0..4).map(|k| m[k][row] * v[k]).sum();
+        }
+        [out[0] / out[3], out[1] / out[3], out[2] / out[3]]
+    }
+
+    fn camera() -> OrbitController {
+        OrbitController {
+            target: [1.0, 2.0, 3.0],
+            yaw: 0.7,
+            pitch: -0.4,
+            ..OrbitController::default()
+        }
+    }
+
+    #[test]
+    fn eye_is_distance_from_target() {
+        let camera = camera();
+        let eye = camera.eye();
+        let offset = [
+            eye[0] - camera.target[0],
+            eye[1] - camera.target[1],
+            eye[2] - camera.target[2],
+        ];
+        assert_close(dot(offset, offset).sqrt(), camera.distance);
+    }
+
+    #[test]
+    fn view_puts_the_target_down_negative_z() {
+        let camera = camera();
+        let target = transform(&camera.view(), camera.target);
+        assert_close(target[0], 0.0);
+        assert_close(target[1], 0.0);
+        assert_close(target[2], -camera.distance);
+        let eye = transform(&camera.view(), camera.eye());
+        for coordinate in eye.iter() {
+            assert_close(*coordinate, 0.0);
+        }
+    }
+
+    #[test]
+    fn projection_maps_near_and_far_to_zero_and_one() {
+        let camera = camera();
+        let projection = camera.projection();
+        assert_close(transform(&projection, [0.0, 0.0, -camera.near])[2], 0.0);
+        assert_close(transform(&projection, [0.0, 0.0, -camera.far])[2], 1.0);
+    }
+
+    #[test]
+    fn projection_scales_by_aspect() {
+        let mut camera = camera();
+        camera.set_aspect(2.0);
+        let projection = camera.projection();
+        assert_close(projection[0][0] * 2.0, projection[1][1]);
+        assert_close(projection[1][1], 1.0 / (camera.fov_y / 2.0).tan());
+    }
+
+    #[test]
+    fn view_projection_is_projection_times_view() {
+        let camera = camera();
+        let point = [0.5, -1.0, 2.0];
+        let combined = transform(&camera.view_projection(), point);
+        let separate = transform(&camera.projection(), transform(&camera.view(), point));
+        for i in 0..3 {
+            assert_close(combined[i], separate[i]);
+        }
+        let target = transform(&camera.view_projection(), camera.target);
+        assert_close(target[0], 0.0);
+        assert_close(target[1], 0.0);
+    }
+}
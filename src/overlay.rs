@@ -0,0 +1,145 @@
+use crate::{Context, DeviceEvent, DeviceId, Scene, WindowEvent};
+
+/// Composes two scenes, drawing `top` over `base` on the same target. Nest overlays to stack
+/// more than two scenes, e.g. `Overlay<World, Overlay<Hud, Console>>`.
+///
+/// Within each hook `top` draws after `base`: both share the managed pass, and `top`'s `draw`
+/// is recorded after `base`'s, so it should load rather than clear the target. Events and
+/// updates reach every child, `top` first.
+pub struct Overlay<Base, Top> {
+    pub base: Base,
+    pub top: Top,
+}
+
+impl<Base: Scene, Top: Scene> Scene for Overlay<Base, Top> {
+    type Args = (Base::Args, Top::Args);
+
+    fn required_features() -> wgpu::Features {
+        Base::required_features() | Top::required_features()
+    }
+
+    fn optional_features() -> wgpu::Features {
+        Base::optional_features() | Top::optional_features()
+    }
+
+    fn new(ctx: &mut Context, device: &wgpu::Device, (base, top): Self::Args) -> Self {
+        Self {
+            base: Base::new(ctx, device, base),
+            top: Top::new(ctx, device, top),
+        }
+    }
+
+    fn update(&mut self, ctx: &mut Context) {
+        self.top.update(ctx);
+        self.base.update(ctx);
+    }
+
+    fn compute(&mut self, encoder: &mut wgpu::CommandEncoder) {
+        self.base.compute(encoder);
+        self.top.compute(encoder);
+    }
+
+    fn has_compute(&self) -> bool {
+        self.base.has_compute() || self.top.has_compute()
+    }
+
+    fn frame_begin(&mut self, view: &wgpu::TextureView, frame_index: u64) {
+        self.base.frame_begin(view, frame_index);
+        self.top.frame_begin(view, frame_index);
+    }
+
+    fn depth_prepass<'a>(&'a mut self, pass: &mut wgpu::RenderPass<'a>) {
+        self.base.depth_prepass(pass);
+        self.top.depth_prepass(pass);
+    }
+
+    fn draw_pass<'a>(&'a mut self, pass: &mut wgpu::RenderPass<'a>) {
+        self.base.draw_pass(pass);
+        self.top.draw_pass(pass);
+    }
+
+    fn draw(&mut self, encoder: &mut wgpu::CommandEncoder, target: &wgpu::TextureView) {
+        self.base.draw(encoder, target);
+        self.top.draw(encoder, target);
+    }
+
+    fn frame_end(&mut self) {
+        self.base.frame_end();
+        self.top.frame_end();
+    }
+
+    fn is_ready(&self) -> bool {
+        self.base.is_ready() && self.top.is_ready()
+    }
+
+    fn clear_color(&self) -> wgpu::Color {
+        self.base.clear_color()
+    }
+
+    fn surface_usage(&self) -> wgpu::TextureUsage {
+        self.base.surface_usage() | self.top.surface_usage()
+    }
+
+    fn configure_surface(&self, desc: &mut wgpu::SwapChainDescriptor) {
+        self.base.configure_surface(desc);
+        self.top.configure_surface(desc);
+    }
+
+    fn present(
+        &mut self,
+        encoder: &mut wgpu::CommandEncoder,
+        source: &wgpu::BindGroup,
+        target: &wgpu::TextureView,
+    ) -> bool {
+        self.top.present(encoder, source, target) || self.base.present(encoder, source, target)
+    }
+
+    fn recreate(
+        &mut self,
+        device: &wgpu::Device,
+        format: wgpu::TextureFormat,
+        width: u32,
+        height: u32,
+        sample_count: u32,
+    ) {
+        self.base
+            .recreate(device, format, width, height, sample_count);
+        self.top
+            .recreate(device, format, width, height, sample_count);
+    }
+
+    fn suspended(&mut self, ctx: &mut Context) {
+        self.top.suspended(ctx);
+        self.base.suspended(ctx);
+    }
+
+    fn resumed(&mut self, ctx: &mut Context) {
+        self.top.resumed(ctx);
+        self.base.resumed(ctx);
+    }
+
+    fn event(&mut self, ctx: &mut Context, event: &WindowEvent) {
+        self.top.event(ctx, event);
+        self.base.event(ctx, event);
+    }
+
+    fn raw_event(&mut self, event: &winit::event::Event<()>) {
+        self.top.raw_event(event);
+        self.base.raw_event(event);
+    }
+
+    fn device_event(&mut self, ctx: &mut Context, device_id: DeviceId, event: &DeviceEvent) {
+        self.top.device_event(ctx, device_id, event);
+        self.base.device_event(ctx, device_id, event);
+    }
+
+    fn device_added(&mut self, ctx: &mut Context, device_id: DeviceId) {
+        self.top.device_added(ctx, device_id);
+        self.base.device_added(ctx, device_id);
+    }
+
+    fn device_removed(&mut self, ctx: &mut Context, device_id: DeviceId) {
+        self.top.device_removed(ctx, device_id);
+        self.base.device_removed(ctx, device_id);
+    }
+}
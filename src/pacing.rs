@@ -0,0 +1,97 @@
+use futures::FutureExt;
+use std::collections::VecDeque;
+use std::future::Future;
+use std::pin::Pin;
+use std::time::Duration;
+
+type Mapping = Pin<Box<dyn Future<Output = Result<(), wgpu::BufferAsyncError>> + Send>>;
+
+/// Bytes copied into each fence
+const FENCE_SIZE: wgpu::BufferAddress = 4;
+
+/// How long `FramePacer::wait` sleeps between checks on the GPU, short next to a frame
+const POLL_INTERVAL: Duration = Duration::from_micros(500);
+
+/// Tracks the frames the GPU is still working on, for `LaunchConfig::max_frames_in_flight` and
+/// `LaunchConfig::max_frame_latency`. wgpu 0.6 has no work-done callback, so each frame ends
+/// with a tiny copy into a fence buffer which is then mapped; the mapping completes once the GPU
+/// has finished the frame.
+pub(crate) struct FramePacer {
+    source: wgpu::Buffer,
+    idle: Vec<wgpu::Buffer>,
+    /// Fence recorded into the frame being prepared, mapped once it's submitted
+    pending: Option<wgpu::Buffer>,
+    in_flight: VecDeque<(wgpu::Buffer, Mapping)>,
+    label: String,
+}
+
+impl FramePacer {
+    pub fn new(device: &wgpu::Device, label_prefix: &str) -> Self {
+        let label = format!("{}Frame fence", label_prefix);
+        let source = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some(&label),
+            size: FENCE_SIZE,
+            usage: wgpu::BufferUsage::COPY_SRC,
+            mapped_at_creation: false,
+        });
+        Self {
+            source,
+            idle: Vec::new(),
+            pending: None,
+            in_flight: VecDeque::new(),
+            label,
+        }
+    }
+
+    /// Retire the frames the GPU has finished, returning how many it's still working on
+    pub fn in_flight(&mut self, device: &wgpu::Device) -> usize {
+        device.poll(wgpu::Maintain::Poll);
+        while let Some((_, mapping)) = self.in_flight.front_mut() {
+            match mapping.now_or_never() {
+                Some(result) => {
+                    result.expect("Map frame fence");
+                    let (fence, _) = self.in_flight.pop_front().unwrap();
+                    fence.unmap();
+                    self.idle.push(fence);
+                }
+                None => break,
+            }
+        }
+        self.in_flight.len()
+    }
+
+    /// Block until fewer than `limit` frames are in flight. With a limit of 1 that's every frame,
+    /// so this waits on the device; waiting on it would otherwise wait for too many frames, so
+    /// this polls until the oldest ones finish instead, sleeping in between.
+    pub fn wait(&mut self, device: &wgpu::Device, limit: usize) {
+        if limit == 1 && !self.in_flight.is_empty() {
+            device.poll(wgpu::Maintain::Wait);
+        }
+        while self.in_flight(device) >= limit {
+            std::thread::sleep(POLL_INTERVAL);
+        }
+    }
+
+    /// Record the frame's fence at the end of its encoder
+    pub fn record(&mut self, device: &wgpu::Device, encoder: &mut wgpu::CommandEncoder) {
+        let label = &self.label;
+        let fence = self.idle.pop().unwrap_or_else(|| {
+            device.create_buffer(&wgpu::BufferDescriptor {
+                label: Some(label),
+                size: FENCE_SIZE,
+                usage: wgpu::BufferUsage::COPY_DST | wgpu::BufferUsage::MAP_READ,
+                mapped_at_creation: false,
+            })
+        });
+        encoder.copy_buffer_to_buffer(&self.source, 0, &fence, 0, FENCE_SIZE);
+        self.pending = Some(fence);
+    }
+
+    /// Start waiting on the fence recorded into the frame just submitted
+    pub fn submitted(&mut self) {
+        if let Some(fence) = self.pending.take() {
+            let mapping = fence.slice(..).map_async(wgpu::MapMode::Read);
+            self.in_flight.push_back((fence, Box::pin(mapping)));
+        }
+    }
+}
@@ -0,0 +1,139 @@
+use crate::readback::padded_bytes_per_row;
+use futures::FutureExt;
+use std::future::Future;
+use std::pin::Pin;
+use winit::dpi::PhysicalSize;
+
+type Mapping = Pin<Box<dyn Future<Output = Result<(), wgpu::BufferAsyncError>> + Send>>;
+
+/// ID buffer for selecting objects under the cursor. Draw object IDs into `view()` as an extra
+/// color attachment of the scene's pass, `pick` the pixel under the cursor after that pass, then
+/// `poll` on later frames until the ID arrives.
+pub struct Picker {
+    texture: wgpu::Texture,
+    view: wgpu::TextureView,
+    size: PhysicalSize<u32>,
+    buffer: wgpu::Buffer,
+    state: PickState,
+    label_prefix: String,
+}
+
+/// Progress of the pick in flight
+enum PickState {
+    Idle,
+    /// The copy has been recorded but possibly not submitted, so the buffer can't be mapped yet
+    Copied,
+    Mapping(Mapping),
+}
+
+impl Picker {
+    /// Format of the ID buffer; pipelines writing IDs must output a `uint` to it
+    pub const FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::R32Uint;
+
+    /// Create an ID buffer of the given size, normally the window's `inner_size()`, labelling
+    /// its objects with `label_prefix`, normally `Context::label_prefix`
+    pub fn new(device: &wgpu::Device, label_prefix: &str, size: PhysicalSize<u32>) -> Self {
+        let (texture, view) = id_texture(device, label_prefix, size);
+        let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some(&format!("{}Pick readback", label_prefix)),
+            size: padded_bytes_per_row(4) as wgpu::BufferAddress,
+            usage: wgpu::BufferUsage::COPY_DST | wgpu::BufferUsage::MAP_READ,
+            mapped_at_creation: false,
+        });
+        Self {
+            texture,
+            view,
+            size,
+            buffer,
+            state: PickState::Idle,
+            label_prefix: label_prefix.into(),
+        }
+    }
+
+    /// Recreate the ID buffer at a new size; a pick in flight still completes
+    pub fn resize(&mut self, device: &wgpu::Device, size: PhysicalSize<u32>) {
+        let (texture, view) = id_texture(device, &self.label_prefix, size);
+        self.texture = texture;
+        self.view = view;
+        self.size = size;
+    }
+
+    /// Attachment to draw IDs into
+    pub fn view(&self) -> &wgpu::TextureView {
+        &self.view
+    }
+
+    /// Record a copy of the ID at pixel (`x`, `y`), such as from `Scene::draw`. Returns false
+    /// without recording anything if the pixel is outside the buffer or a pick is in flight.
+    pub fn pick(&mut self, encoder: &mut wgpu::CommandEncoder, x: u32, y: u32) -> bool {
+        let in_bounds = x < self.size.width && y < self.size.height;
+        if !in_bounds || !matches!(self.state, PickState::Idle) {
+            return false;
+        }
+
+        encoder.copy_texture_to_buffer(
+            wgpu::TextureCopyView {
+                texture: &self.texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d { x, y, z: 0 },
+            },
+            wgpu::BufferCopyView {
+                buffer: &self.buffer,
+                layout: wgpu::TextureDataLayout {
+                    offset: 0,
+                    bytes_per_row: padded_bytes_per_row(4),
+                    rows_per_image: 1,
+                },
+            },
+            wgpu::Extent3d {
+                width: 1,
+                height: 1,
+                depth: 1,
+            },
+        );
+        self.state = PickState::Copied;
+        true
+    }
+
+    /// Check on the pick in flight, returning its ID once it has been read back. Call this after
+    /// the encoder passed to `pick` has been submitted, such as from `Scene::update`; the copy
+    /// completes as the device is polled, which every submission does.
+    pub fn poll(&mut self) -> Option<u32> {
+        match &mut self.state {
+            PickState::Idle => None,
+            PickState::Copied => {
+                let mapping = self.buffer.slice(..4).map_async(wgpu::MapMode::Read);
+                self.state = PickState::Mapping(Box::pin(mapping));
+                None
+            }
+            PickState::Mapping(mapping) => {
+                let result = mapping.now_or_never()?;
+                result.expect("Map pick buffer");
+                let id = {
+                    let data = self.buffer.slice(..4).get_mapped_range();
+                    u32::from_ne_bytes([data[0], data[1], data[2], data[3]])
+                };
+                self.buffer.unmap();
+                self.state = PickState::Idle;
+                Some(id)
+            }
+        }
+    }
+}
+
+fn id_texture(
+    device: &wgpu::Device,
+    label_prefix: &str,
+    size: PhysicalSize<u32>,
+) -> (wgpu::Texture, wgpu::TextureView) {
+    let texture = crate::target::create_texture(
+        device,
+        &format!("{}Picking IDs", label_prefix),
+        size,
+        1,
+        Picker::FORMAT,
+        wgpu::TextureUsage::OUTPUT_ATTACHMENT | wgpu::TextureUsage::COPY_SRC,
+    );
+    let view = texture.create_view(&Default::default());
+    (texture, view)
+}
@@ -0,0 +1,342 @@
+use crate::{Context, DEPTH_FORMAT};
+
+/// How fragments combine with the color already in the target
+#[derive(Debug, Clone, PartialEq)]
+pub enum BlendMode {
+    /// Overwrite the target
+    Opaque,
+    /// Blend by the fragment's alpha, for colors which aren't multiplied by it
+    AlphaBlend,
+    /// Blend by the fragment's alpha, for colors which are already multiplied by it
+    PremultipliedAlpha,
+    /// Add the fragment's color and alpha to the target's, such as for glows and particles
+    Additive,
+    /// The given color and alpha blending
+    Custom {
+        color: wgpu::BlendDescriptor,
+        alpha: wgpu::BlendDescriptor,
+    },
+}
+
+impl BlendMode {
+    /// The (color, alpha) blending this mode stands for
+    pub fn descriptors(&self) -> (wgpu::BlendDescriptor, wgpu::BlendDescriptor) {
+        use wgpu::{BlendDescriptor, BlendFactor, BlendOperation};
+        let blend = |src_factor, dst_factor| BlendDescriptor {
+            src_factor,
+            dst_factor,
+            operation: BlendOperation::Add,
+        };
+        match self {
+            Self::Opaque => (BlendDescriptor::REPLACE, BlendDescriptor::REPLACE),
+            Self::AlphaBlend => (
+                blend(BlendFactor::SrcAlpha, BlendFactor::OneMinusSrcAlpha),
+                blend(BlendFactor::One, BlendFactor::OneMinusSrcAlpha),
+            ),
+            Self::PremultipliedAlpha => (
+                blend(BlendFactor::One, BlendFactor::OneMinusSrcAlpha),
+                blend(BlendFactor::One, BlendFactor::OneMinusSrcAlpha),
+            ),
+            Self::Additive => (
+                blend(BlendFactor::One, BlendFactor::One),
+                blend(BlendFactor::One, BlendFactor::One),
+            ),
+            Self::Custom { color, alpha } => (color.clone(), alpha.clone()),
+        }
+    }
+}
+
+/// Offset added to each fragment's depth before it's tested and written, for
+/// `PipelineConfig::depth_bias`. Shadow-map passes need it to keep surfaces from shadowing
+/// themselves, known as shadow acne.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DepthBias {
+    /// Constant offset, in units of the smallest depth difference the format can represent
+    pub constant: i32,
+    /// Offset per unit of the polygon's depth slope, which grows as it turns away from the view
+    pub slope_scale: f32,
+    /// Largest offset applied; 0.0 leaves it unclamped
+    pub clamp: f32,
+}
+
+impl DepthBias {
+    /// No bias
+    pub const NONE: Self = Self {
+        constant: 0,
+        slope_scale: 0.0,
+        clamp: 0.0,
+    };
+
+    /// A starting point for rendering shadow maps; raise it if acne remains, or lower it if
+    /// shadows detach from their casters
+    pub const SHADOW_MAP: Self = Self {
+        constant: 2,
+        slope_scale: 2.0,
+        clamp: 0.0,
+    };
+}
+
+impl Default for DepthBias {
+    fn default() -> Self {
+        Self::NONE
+    }
+}
+
+/// The parts of a render pipeline which usually differ between pipelines, for
+/// `simple_pipeline`. Create one with `PipelineConfig::new` and adjust the fields as needed.
+#[derive(Clone)]
+pub struct PipelineConfig<'a> {
+    /// Name of the pipeline and its layout, "Pipeline" if unset
+    pub label: Option<&'a str>,
+    /// Prepended to `label`, normally `Context::label_prefix`
+    pub label_prefix: &'static str,
+    /// Bind group layouts used by the shaders; `None` for shaders that bind nothing
+    pub layout: Option<&'a wgpu::PipelineLayout>,
+    /// Vertex shader, with entry point `main`
+    pub vertex_shader: &'a wgpu::ShaderModule,
+    /// Fragment shader, with entry point `main`
+    pub fragment_shader: &'a wgpu::ShaderModule,
+    pub vertex_buffers: &'a [wgpu::VertexBufferDescriptor<'a>],
+    pub index_format: wgpu::IndexFormat,
+    pub topology: wgpu::PrimitiveTopology,
+    pub blend: BlendMode,
+    /// Depth comparison against the `DEPTH_FORMAT` attachment, which pipelines used in
+    /// `Scene::draw_pass` need; `None` for passes without a depth attachment
+    pub depth_compare: Option<wgpu::CompareFunction>,
+    /// Whether passing fragments write their depth, if `depth_compare` is set
+    pub depth_write: bool,
+    /// Offset applied to each fragment's depth, such as `DepthBias::SHADOW_MAP`
+    pub depth_bias: DepthBias,
+    pub cull_mode: wgpu::CullMode,
+    /// Format of the color target, normally `Context::format`
+    pub format: wgpu::TextureFormat,
+    /// Samples per pixel of the color target, normally `Context::sample_count`
+    pub sample_count: u32,
+}
+
+impl<'a> PipelineConfig<'a> {
+    /// Opaque triangle lists without culling, depth tested with `Less` (or `Greater` when
+    /// `LaunchConfig::depth_clear` is 0.0, for reverse-Z, and not at all without a
+    /// `LaunchConfig::depth_buffer`) and drawn to the scene's render target
+    pub fn new(
+        ctx: &Context,
+        vertex_shader: &'a wgpu::ShaderModule,
+        fragment_shader: &'a wgpu::ShaderModule,
+    ) -> Self {
+        Self {
+            label: None,
+            label_prefix: ctx.label_prefix(),
+            layout: None,
+            vertex_shader,
+            fragment_shader,
+            vertex_buffers: &[],
+            index_format: wgpu::IndexFormat::Uint16,
+            topology: wgpu::PrimitiveTopology::TriangleList,
+            blend: BlendMode::Opaque,
+            depth_compare: if !ctx.depth_buffer() {
+                None
+            } else if ctx.depth_clear() == 0.0 {
+                Some(wgpu::CompareFunction::Greater)
+            } else {
+                Some(wgpu::CompareFunction::Less)
+            },
+            depth_write: true,
+            depth_bias: DepthBias::NONE,
+            cull_mode: wgpu::CullMode::None,
+            format: ctx.format(),
+            sample_count: ctx.sample_count(),
+        }
+    }
+
+    /// Like `new`, but culling back faces
+    pub fn new_3d(
+        ctx: &Context,
+        vertex_shader: &'a wgpu::ShaderModule,
+        fragment_shader: &'a wgpu::ShaderModule,
+    ) -> Self {
+        Self {
+            cull_mode: wgpu::CullMode::Back,
+            ..Self::new(ctx, vertex_shader, fragment_shader)
+        }
+    }
+
+    /// Like `new`, but alpha blended and without depth testing, drawing in submission order
+    pub fn new_2d(
+        ctx: &Context,
+        vertex_shader: &'a wgpu::ShaderModule,
+        fragment_shader: &'a wgpu::ShaderModule,
+    ) -> Self {
+        Self {
+            blend: BlendMode::AlphaBlend,
+            depth_compare: None,
+            ..Self::new(ctx, vertex_shader, fragment_shader)
+        }
+    }
+}
+
+/// Create a render pipeline from the given config, filling the rest of the descriptor with
+/// common defaults: counter-clockwise front faces and all samples and color channels enabled
+pub fn simple_pipeline(device: &wgpu::Device, config: PipelineConfig) -> wgpu::RenderPipeline {
+    let label = format!(
+        "{}{}",
+        config.label_prefix,
+        config.label.unwrap_or("Pipeline")
+    );
+    let empty_layout;
+    let layout = match config.layout {
+        Some(layout) => layout,
+        None => {
+            empty_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: Some(&label),
+                bind_group_layouts: &[],
+                push_constant_ranges: &[],
+            });
+            &empty_layout
+        }
+    };
+
+    let (color_blend, alpha_blend) = config.blend.descriptors();
+    let depth_write_enabled = config.depth_write;
+    let depth_stencil_state =
+        config
+            .depth_compare
+            .map(|depth_compare| wgpu::DepthStencilStateDescriptor {
+                format: DEPTH_FORMAT,
+                depth_write_enabled,
+                depth_compare,
+                stencil: wgpu::StencilStateDescriptor::default(),
+            });
+
+    device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+        label: Some(&label),
+        layout: Some(layout),
+        vertex_stage: wgpu::ProgrammableStageDescriptor {
+            module: config.vertex_shader,
+            entry_point: "main",
+        },
+        fragment_stage: Some(wgpu::ProgrammableStageDescriptor {
+            module: config.fragment_shader,
+            entry_point: "main",
+        }),
+        rasterization_state: Some(wgpu::RasterizationStateDescriptor {
+            front_face: wgpu::FrontFace::Ccw,
+            cull_mode: config.cull_mode,
+            depth_bias: config.depth_bias.constant,
+            depth_bias_slope_scale: config.depth_bias.slope_scale,
+            depth_bias_clamp: config.depth_bias.clamp,
+            ..Default::default()
+        }),
+        primitive_topology: config.topology,
+        color_states: &[wgpu::ColorStateDescriptor {
+            format: config.format,
+            color_blend,
+            alpha_blend,
+            write_mask: wgpu::ColorWrite::ALL,
+        }],
+        depth_stencil_state,
+        vertex_state: wgpu::VertexStateDescriptor {
+            index_format: config.index_format,
+            vertex_buffers: config.vertex_buffers,
+        },
+        sample_count: config.sample_count,
+        sample_mask: !0,
+        alpha_to_coverage_enabled: false,
+    })
+}
+
+/// A pipeline built twice from one config, filled and as a line list, for switching to
+/// wireframe with `Context::set_wireframe`. wgpu 0.6 has no polygon modes, so the line variant
+/// must be drawn with edge indices, as `Mesh::draw_as` does for meshes made with
+/// `Mesh::with_edges`; it's never culled.
+pub struct WireframePipeline {
+    fill: wgpu::RenderPipeline,
+    line: wgpu::RenderPipeline,
+}
+
+impl WireframePipeline {
+    /// Create both variants; `config.topology` must be `TriangleList`
+    pub fn new(device: &wgpu::Device, config: PipelineConfig) -> Self {
+        assert_eq!(
+            config.topology,
+            wgpu::PrimitiveTopology::TriangleList,
+            "Wireframes are only built from triangle lists"
+        );
+        let line = simple_pipeline(
+            device,
+            PipelineConfig {
+                topology: wgpu::PrimitiveTopology::LineList,
+                cull_mode: wgpu::CullMode::None,
+                ..config.clone()
+            },
+        );
+        Self {
+            fill: simple_pipeline(device, config),
+            line,
+        }
+    }
+
+    /// The line variant with `wireframe`, or else the filled one
+    pub fn get(&self, wireframe: bool) -> &wgpu::RenderPipeline {
+        if wireframe {
+            &self.line
+        } else {
+            &self.fill
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wgpu::{BlendDescriptor, BlendFactor, BlendOperation};
+
+    #[test]
+    fn opaque_replaces() {
+        assert_eq!(
+            BlendMode::Opaque.descriptors(),
+            (BlendDescriptor::REPLACE, BlendDescriptor::REPLACE)
+        );
+    }
+
+    #[test]
+    fn alpha_blend_weights_color_by_source_alpha() {
+        let (color, alpha) = BlendMode::AlphaBlend.descriptors();
+        assert_eq!(color.src_factor, BlendFactor::SrcAlpha);
+        assert_eq!(color.dst_factor, BlendFactor::OneMinusSrcAlpha);
+        assert_eq!(alpha.src_factor, BlendFactor::One);
+        assert_eq!(alpha.dst_factor, BlendFactor::OneMinusSrcAlpha);
+    }
+
+    #[test]
+    fn premultiplied_and_additive_blend_the_same_in_both_channels() {
+        for mode in &[BlendMode::PremultipliedAlpha, BlendMode::Additive] {
+            let (color, alpha) = mode.descriptors();
+            assert_eq!(color, alpha);
+            assert_eq!(color.src_factor, BlendFactor::One);
+            assert_eq!(color.operation, BlendOperation::Add);
+        }
+        assert_eq!(
+            BlendMode::Additive.descriptors().0.dst_factor,
+            BlendFactor::One
+        );
+    }
+
+    #[test]
+    fn custom_is_passed_through() {
+        let color = BlendDescriptor {
+            src_factor: BlendFactor::DstColor,
+            dst_factor: BlendFactor::Zero,
+            operation: BlendOperation::Max,
+        };
+        let alpha = BlendDescriptor {
+            src_factor: BlendFactor::Zero,
+            dst_factor: BlendFactor::One,
+            operation: BlendOperation::Subtract,
+        };
+        let mode = BlendMode::Custom {
+            color: color.clone(),
+            alpha: alpha.clone(),
+        };
+        assert_eq!(mode.descriptors(), (color, alpha));
+    }
+}
@@ -0,0 +1,66 @@
+use std::convert::TryInto;
+
+/// One range of push constants, needing `Features::PUSH_CONSTANTS`. Pass `range()` in the
+/// pipeline layout's `push_constant_ranges`, then write the values each draw with `set`, which
+/// takes raw bytes in the layout the shader declares.
+#[derive(Debug, Clone)]
+pub struct PushConstants {
+    stages: wgpu::ShaderStage,
+    offset: u32,
+    size: u32,
+}
+
+impl PushConstants {
+    /// Describe `size` bytes at `offset` visible to `stages`, panicking if the device didn't
+    /// enable push constants, the range isn't 4-byte aligned, or it exceeds
+    /// `Limits::max_push_constant_size`
+    pub fn new(device: &wgpu::Device, stages: wgpu::ShaderStage, offset: u32, size: u32) -> Self {
+        assert!(
+            device.features().contains(wgpu::Features::PUSH_CONSTANTS),
+            "Push constants need Features::PUSH_CONSTANTS"
+        );
+        let alignment = wgpu::PUSH_CONSTANT_ALIGNMENT;
+        assert!(
+            offset.is_multiple_of(alignment) && size.is_multiple_of(alignment),
+            "Push constant range {}..{} isn't aligned to {} bytes",
+            offset,
+            offset + size,
+            alignment
+        );
+        let limit = device.limits().max_push_constant_size;
+        assert!(
+            offset + size <= limit,
+            "Push constant range {}..{} exceeds the device's limit of {} bytes",
+            offset,
+            offset + size,
+            limit
+        );
+        Self {
+            stages,
+            offset,
+            size,
+        }
+    }
+
+    /// Range to declare in the pipeline layout
+    pub fn range(&self) -> wgpu::PushConstantRange {
+        wgpu::PushConstantRange {
+            stages: self.stages,
+            range: self.offset..self.offset + self.size,
+        }
+    }
+
+    /// Write the whole range from `data`, which must be exactly `size` bytes
+    pub fn set(&self, pass: &mut wgpu::RenderPass, data: &[u8]) {
+        assert_eq!(
+            data.len(),
+            self.size as usize,
+            "Push constant data doesn't match the range's size"
+        );
+        let words: Vec<u32> = data
+            .chunks_exact(4)
+            .map(|word| u32::from_ne_bytes(word.try_into().unwrap()))
+            .collect();
+        pass.set_push_constants(self.stages, self.offset, &words);
+    }
+}
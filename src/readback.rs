@@ -0,0 +1,88 @@
+use winit::dpi::PhysicalSize;
+
+/// Round a row length up to the alignment wgpu requires for buffer/texture copies
+pub(crate) fn padded_bytes_per_row(bytes_per_row: u32) -> u32 {
+    let align = wgpu::COPY_BYTES_PER_ROW_ALIGNMENT;
+    bytes_per_row.div_ceil(align) * align
+}
+
+/// Copy a single-sampled texture back to the host, returning its tightly packed rows
+pub(crate) fn read_texture(
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    texture: &wgpu::Texture,
+    size: PhysicalSize<u32>,
+    bytes_per_pixel: u32,
+    label_prefix: &str,
+) -> Vec<u8> {
+    let label = format!("{}Readback", label_prefix);
+    let bytes_per_row = size.width * bytes_per_pixel;
+    let padded_bytes_per_row = padded_bytes_per_row(bytes_per_row);
+
+    let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some(&label),
+        size: (padded_bytes_per_row * size.height) as wgpu::BufferAddress,
+        usage: wgpu::BufferUsage::COPY_DST | wgpu::BufferUsage::MAP_READ,
+        mapped_at_creation: false,
+    });
+
+    let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+        label: Some(&label),
+    });
+    encoder.copy_texture_to_buffer(
+        wgpu::TextureCopyView {
+            texture,
+            mip_level: 0,
+            origin: wgpu::Origin3d::ZERO,
+        },
+        wgpu::BufferCopyView {
+            buffer: &buffer,
+            layout: wgpu::TextureDataLayout {
+                offset: 0,
+                bytes_per_row: padded_bytes_per_row,
+                rows_per_image: size.height,
+            },
+        },
+        wgpu::Extent3d {
+            width: size.width,
+            height: size.height,
+            depth: 1,
+        },
+    );
+    queue.submit(Some(encoder.finish()));
+
+    // Wait for the copy, then strip the row padding
+    let slice = buffer.slice(..);
+    let mapping = slice.map_async(wgpu::MapMode::Read);
+    device.poll(wgpu::Maintain::Wait);
+    futures::executor::block_on(mapping).expect("Map readback buffer");
+
+    let padded = slice.get_mapped_range();
+    let mut data = Vec::with_capacity((bytes_per_row * size.height) as usize);
+    for row in padded.chunks(padded_bytes_per_row as usize) {
+        data.extend_from_slice(&row[..bytes_per_row as usize]);
+    }
+    data
+}
+
+/// Swap the red and blue channels of packed 8-bit BGRA pixels in place
+pub(crate) fn bgra_to_rgba(data: &mut [u8]) {
+    for pixel in data.chunks_exact_mut(4) {
+        pixel.swap(0, 2);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rows_are_padded_to_the_copy_alignment() {
+        let align = wgpu::COPY_BYTES_PER_ROW_ALIGNMENT;
+        assert_eq!(padded_bytes_per_row(0), 0);
+        assert_eq!(padded_bytes_per_row(1), align);
+        assert_eq!(padded_bytes_per_row(align), align);
+        assert_eq!(padded_bytes_per_row(align + 1), 2 * align);
+        assert_eq!(padded_bytes_per_row(3 * 4), align);
+    }
+}
@@ -0,0 +1,113 @@
+use crate::readback::{bgra_to_rgba, read_texture};
+use crate::target::create_texture;
+#[cfg(feature = "video")]
+use crate::video::VideoEncoder;
+use crate::SWAPCHAIN_FORMAT;
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use winit::dpi::PhysicalSize;
+
+/// Where a `Recorder`'s frames go
+pub(crate) enum Sink {
+    /// Numbered images in a directory, for `LaunchConfig::record_to`
+    Frames(PathBuf),
+    /// A video, for `LaunchConfig::record_video`
+    #[cfg(feature = "video")]
+    Video(VideoEncoder),
+}
+
+/// Copy of each presented frame, saved to disk for `LaunchConfig::record_to` or
+/// `LaunchConfig::record_video`. wgpu 0.6 can't copy out of swapchain images, so frames are
+/// presented to this texture as well.
+pub(crate) struct Recorder {
+    texture: wgpu::Texture,
+    view: wgpu::TextureView,
+    size: PhysicalSize<u32>,
+    sink: Sink,
+}
+
+impl Recorder {
+    pub fn new(
+        device: &wgpu::Device,
+        label_prefix: &str,
+        size: PhysicalSize<u32>,
+        sink: Sink,
+    ) -> Self {
+        match &sink {
+            Sink::Frames(directory) => {
+                fs::create_dir_all(directory).expect("Create recording directory")
+            }
+            #[cfg(feature = "video")]
+            Sink::Video(_) => {}
+        }
+        let texture = create_texture(
+            device,
+            &format!("{}Recorded frame", label_prefix),
+            size,
+            1,
+            SWAPCHAIN_FORMAT,
+            wgpu::TextureUsage::OUTPUT_ATTACHMENT | wgpu::TextureUsage::COPY_SRC,
+        );
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        Self {
+            texture,
+            view,
+            size,
+            sink,
+        }
+    }
+
+    /// Recreate the texture at the new window size. A video can't change size, so it keeps the
+    /// size it started at and frames are stretched to it. The empty size of a minimized window
+    /// is skipped, keeping the last one.
+    pub fn resize(&mut self, device: &wgpu::Device, label_prefix: &str, size: PhysicalSize<u32>) {
+        if size.width == 0 || size.height == 0 {
+            return;
+        }
+        match &mut self.sink {
+            Sink::Frames(directory) => {
+                let sink = Sink::Frames(std::mem::take(directory));
+                *self = Self::new(device, label_prefix, size, sink);
+            }
+            #[cfg(feature = "video")]
+            Sink::Video(_) => {}
+        }
+    }
+
+    /// Target to present the frame to, alongside the swapchain image
+    pub fn view(&self) -> &wgpu::TextureView {
+        &self.view
+    }
+
+    /// Read back the frame, once submitted, and write it out as `frame_<index>.ppm` or append it
+    /// to the video
+    pub fn save(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        label_prefix: &str,
+        frame_index: u64,
+    ) -> io::Result<()> {
+        let mut pixels = read_texture(device, queue, &self.texture, self.size, 4, label_prefix);
+        bgra_to_rgba(&mut pixels);
+        match &mut self.sink {
+            Sink::Frames(directory) => {
+                let path = directory.join(format!("frame_{:06}.ppm", frame_index));
+                write_ppm(&path, self.size, &pixels)
+            }
+            #[cfg(feature = "video")]
+            Sink::Video(encoder) => encoder.write(&pixels),
+        }
+    }
+}
+
+/// Write RGBA8 pixels as a binary PPM, which drops the alpha channel
+fn write_ppm(path: &Path, size: PhysicalSize<u32>, pixels: &[u8]) -> io::Result<()> {
+    let mut file = BufWriter::new(File::create(path)?);
+    write!(file, "P6\n{} {}\n255\n", size.width, size.height)?;
+    for pixel in pixels.chunks_exact(4) {
+        file.write_all(&pixel[..3])?;
+    }
+    file.flush()
+}
@@ -0,0 +1,65 @@
+use std::num::NonZeroU8;
+
+/// Largest anisotropy clamp wgpu accepts
+const MAX_ANISOTROPY: u8 = 16;
+
+/// Settings for `sampler`. The default is a trilinear sampler clamped to the edge.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SamplerConfig {
+    pub address_mode_u: wgpu::AddressMode,
+    pub address_mode_v: wgpu::AddressMode,
+    pub address_mode_w: wgpu::AddressMode,
+    pub mag_filter: wgpu::FilterMode,
+    pub min_filter: wgpu::FilterMode,
+    pub mipmap_filter: wgpu::FilterMode,
+    /// Maximum anisotropy; must be a power of two no greater than 16, and requires all filters to
+    /// be `Linear`. On devices without anisotropic filtering, wgpu drops the clamp when creating
+    /// the sampler, leaving it trilinear, so it's always safe to request; wgpu 0.6 doesn't
+    /// expose whether that happened.
+    pub anisotropy_clamp: Option<u8>,
+}
+
+impl Default for SamplerConfig {
+    fn default() -> Self {
+        Self {
+            address_mode_u: wgpu::AddressMode::ClampToEdge,
+            address_mode_v: wgpu::AddressMode::ClampToEdge,
+            address_mode_w: wgpu::AddressMode::ClampToEdge,
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            mipmap_filter: wgpu::FilterMode::Linear,
+            anisotropy_clamp: None,
+        }
+    }
+}
+
+/// Create a sampler from `config` labelled `label`, panicking with a descriptive message if it is
+/// invalid
+pub fn sampler(device: &wgpu::Device, label: &str, config: SamplerConfig) -> wgpu::Sampler {
+    if let Some(clamp) = config.anisotropy_clamp {
+        assert!(
+            clamp.is_power_of_two() && clamp <= MAX_ANISOTROPY,
+            "Anisotropy clamp must be a power of two no greater than {}, got {}",
+            MAX_ANISOTROPY,
+            clamp
+        );
+        assert!(
+            [config.mag_filter, config.min_filter, config.mipmap_filter]
+                .iter()
+                .all(|&filter| filter == wgpu::FilterMode::Linear),
+            "Anisotropic filtering requires linear mag, min and mipmap filters"
+        );
+    }
+
+    device.create_sampler(&wgpu::SamplerDescriptor {
+        label: Some(label),
+        address_mode_u: config.address_mode_u,
+        address_mode_v: config.address_mode_v,
+        address_mode_w: config.address_mode_w,
+        mag_filter: config.mag_filter,
+        min_filter: config.min_filter,
+        mipmap_filter: config.mipmap_filter,
+        anisotropy_clamp: config.anisotropy_clamp.and_then(NonZeroU8::new),
+        ..Default::default()
+    })
+}
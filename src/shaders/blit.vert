@@ -0,0 +1,14 @@
+#version 450
+
+layout(location = 0) out vec2 uv;
+
+out gl_PerVertex {
+    vec4 gl_Position;
+};
+
+// Fullscreen triangle; uv's v axis points down the texture
+void main() {
+    vec2 position = vec2((gl_VertexIndex << 1) & 2, gl_VertexIndex & 2);
+    uv = vec2(position.x, 1.0 - position.y);
+    gl_Position = vec4(position * 2.0 - 1.0, 0.0, 1.0);
+}
@@ -0,0 +1,8 @@
+glslc -O blit.vert -o blit.vert.spv
+glslc -O blit.frag -o blit.frag.spv
+glslc -O colored.vert -o colored.vert.spv
+glslc -O colored.frag -o colored.frag.spv
+glslc -O debug_lines.vert -o debug_lines.vert.spv
+glslc -O tonemap_reinhard.frag -o tonemap_reinhard.frag.spv
+glslc -O tonemap_aces.frag -o tonemap_aces.frag.spv
+glslc -O fxaa.frag -o fxaa.frag.spv
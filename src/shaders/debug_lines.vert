@@ -0,0 +1,20 @@
+#version 450
+
+layout(location = 0) in vec3 position;
+layout(location = 1) in vec4 color;
+
+layout(location = 0) out vec4 v_color;
+
+layout(set = 0, binding = 0) uniform Camera {
+    mat4 view_projection;
+};
+
+out gl_PerVertex {
+    vec4 gl_Position;
+};
+
+// World space line vertices, each with its own color
+void main() {
+    v_color = color;
+    gl_Position = view_projection * vec4(position, 1.0);
+}
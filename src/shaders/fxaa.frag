@@ -0,0 +1,41 @@
+#version 450
+
+layout(location = 0) in vec2 uv;
+layout(location = 0) out vec4 outColor;
+
+layout(set = 0, binding = 0) uniform texture2D source;
+layout(set = 0, binding = 1) uniform sampler source_sampler;
+
+// FXAA after Timothy Lottes' original, in its common reduced form: blur along the direction of
+// the local luma gradient, unless that oversteps the neighbourhood's luma range
+const float REDUCE_MIN = 1.0 / 128.0;
+const float REDUCE_MUL = 1.0 / 8.0;
+const float SPAN_MAX = 8.0;
+const vec3 LUMA = vec3(0.299, 0.587, 0.114);
+
+vec3 fetch(vec2 coord) {
+    return texture(sampler2D(source, source_sampler), coord).rgb;
+}
+
+void main() {
+    vec2 texel = 1.0 / vec2(textureSize(sampler2D(source, source_sampler), 0));
+    float nw = dot(fetch(uv + vec2(-1.0, -1.0) * texel), LUMA);
+    float ne = dot(fetch(uv + vec2(1.0, -1.0) * texel), LUMA);
+    float sw = dot(fetch(uv + vec2(-1.0, 1.0) * texel), LUMA);
+    float se = dot(fetch(uv + vec2(1.0, 1.0) * texel), LUMA);
+    vec4 center = texture(sampler2D(source, source_sampler), uv);
+    float m = dot(center.rgb, LUMA);
+    float lumaMin = min(m, min(min(nw, ne), min(sw, se)));
+    float lumaMax = max(m, max(max(nw, ne), max(sw, se)));
+
+    vec2 dir = vec2(-((nw + ne) - (sw + se)), (nw + sw) - (ne + se));
+    float reduce = max((nw + ne + sw + se) * (0.25 * REDUCE_MUL), REDUCE_MIN);
+    float scale = 1.0 / (min(abs(dir.x), abs(dir.y)) + reduce);
+    dir = clamp(dir * scale, -SPAN_MAX, SPAN_MAX) * texel;
+
+    vec3 a = 0.5 * (fetch(uv + dir * (1.0 / 3.0 - 0.5)) + fetch(uv + dir * (2.0 / 3.0 - 0.5)));
+    vec3 b = a * 0.5 + 0.25 * (fetch(uv - dir * 0.5) + fetch(uv + dir * 0.5));
+    float lumaB = dot(b, LUMA);
+    vec3 color = (lumaB < lumaMin || lumaB > lumaMax) ? a : b;
+    outColor = vec4(color, center.a);
+}
@@ -0,0 +1,15 @@
+#version 450
+
+layout(location = 0) in vec2 uv;
+layout(location = 0) out vec4 outColor;
+
+layout(set = 0, binding = 0) uniform texture2D source;
+layout(set = 0, binding = 1) uniform sampler source_sampler;
+
+// Krzysztof Narkowicz's fit of the ACES filmic tone curve
+void main() {
+    vec4 hdr = texture(sampler2D(source, source_sampler), uv);
+    vec3 x = hdr.rgb;
+    vec3 mapped = (x * (2.51 * x + 0.03)) / (x * (2.43 * x + 0.59) + 0.14);
+    outColor = vec4(clamp(mapped, 0.0, 1.0), hdr.a);
+}
@@ -0,0 +1,12 @@
+#version 450
+
+layout(location = 0) in vec2 uv;
+layout(location = 0) out vec4 outColor;
+
+layout(set = 0, binding = 0) uniform texture2D source;
+layout(set = 0, binding = 1) uniform sampler source_sampler;
+
+void main() {
+    vec4 hdr = texture(sampler2D(source, source_sampler), uv);
+    outColor = vec4(hdr.rgb / (hdr.rgb + 1.0), hdr.a);
+}
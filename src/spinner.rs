@@ -0,0 +1,89 @@
+use crate::colored::ColoredRenderer;
+use crate::Context;
+use std::f32::consts::TAU;
+use std::time::Instant;
+
+/// Number of spokes around the spinner
+const SPOKES: usize = 12;
+
+/// Inner and outer radius of the spokes, and their width, in logical pixels
+const INNER_RADIUS: f32 = 10.0;
+const OUTER_RADIUS: f32 = 24.0;
+const SPOKE_WIDTH: f32 = 4.0;
+
+/// Turns per second
+const SPEED: f32 = 1.0;
+
+/// Spinner drawn in the middle of the window for `LaunchConfig::loading_indicator` while
+/// `Scene::is_ready` is false: a ring of spokes whose highlight goes around once a second
+pub(crate) struct Spinner {
+    renderer: ColoredRenderer,
+    start: Instant,
+}
+
+impl Spinner {
+    /// Create a spinner drawing to targets of the given format
+    pub fn new(device: &wgpu::Device, format: wgpu::TextureFormat, label_prefix: &str) -> Self {
+        Self {
+            renderer: ColoredRenderer::new(device, format, &format!("{}Spinner", label_prefix)),
+            start: Instant::now(),
+        }
+    }
+
+    /// Clear `target` to `clear_color`, then draw the spinner in the middle of the window
+    pub fn draw(
+        &mut self,
+        ctx: &Context,
+        encoder: &mut wgpu::CommandEncoder,
+        target: &wgpu::TextureView,
+        clear_color: wgpu::Color,
+    ) {
+        encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            color_attachments: &[wgpu::RenderPassColorAttachmentDescriptor {
+                attachment: target,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(clear_color),
+                    store: true,
+                },
+            }],
+            depth_stencil_attachment: None,
+        });
+
+        let window_size = ctx.physical_size();
+        let scale = [
+            2.0 / window_size.width as f32,
+            2.0 / window_size.height as f32,
+        ];
+        let pixel = ctx.scale_factor() as f32;
+        let head = self.start.elapsed().as_secs_f32() * SPEED * SPOKES as f32;
+
+        let mut vertices = Vec::new();
+        for spoke in 0..SPOKES {
+            // Spokes fade out behind the highlighted one
+            let behind = (head - spoke as f32).rem_euclid(SPOKES as f32);
+            let alpha = 1.0 - behind / SPOKES as f32 * 0.85;
+            let color = [1.0, 1.0, 1.0, alpha];
+
+            let (sin, cos) = (spoke as f32 / SPOKES as f32 * TAU).sin_cos();
+            let along = |radius: f32, side: f32| {
+                let x = (cos * radius - sin * side) * pixel;
+                let y = (sin * radius + cos * side) * pixel;
+                [x * scale[0], y * scale[1]]
+            };
+            let half = SPOKE_WIDTH / 2.0;
+            let corners = [
+                along(INNER_RADIUS, -half),
+                along(OUTER_RADIUS, -half),
+                along(OUTER_RADIUS, half),
+                along(INNER_RADIUS, half),
+            ];
+            for &i in &[0, 1, 2, 0, 2, 3] {
+                vertices.extend_from_slice(&corners[i]);
+                vertices.extend_from_slice(&color);
+            }
+        }
+        self.renderer
+            .draw(ctx.device(), ctx.queue(), encoder, target, &vertices);
+    }
+}
@@ -0,0 +1,155 @@
+use crate::texture::bits_per_texel;
+use std::collections::VecDeque;
+use std::time::Duration;
+use winit::dpi::PhysicalSize;
+
+/// Approximate GPU memory held by the render targets `launch` manages: the depth buffer,
+/// intermediate and recording textures. wgpu 0.6 reports no allocation statistics, so this
+/// leaves out the swapchain images, the scene's own resources and any driver overhead.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct MemoryStats {
+    /// Number of textures
+    pub textures: u32,
+    /// Combined size of their texels, in bytes
+    pub texture_bytes: u64,
+}
+
+impl MemoryStats {
+    /// Count a single-level 2D texture
+    pub(crate) fn add_texture(
+        &mut self,
+        size: PhysicalSize<u32>,
+        format: wgpu::TextureFormat,
+        sample_count: u32,
+    ) {
+        let texels = size.width as u64 * size.height as u64 * sample_count as u64;
+        self.textures += 1;
+        self.texture_bytes += texels * bits_per_texel(format) as u64 / 8;
+    }
+}
+
+/// Number of frames `FrameStats` remembers
+pub(crate) const FRAME_HISTORY: usize = 120;
+
+/// Wall-clock time between recent frames, measured by `launch` regardless of
+/// `LaunchConfig::fixed_dt` and leaving out time spent paused, hidden or idle under
+/// `RedrawPolicy::Reactive`, and counts of how frames fared.
+///
+/// wgpu 0.6 doesn't report presentation timing, nor whether the compositor showed a frame
+/// once it was queued; under `Mailbox` a queued frame may still be replaced before it's seen.
+/// What `launch` can count is frames submitted for presentation and frames skipped because no
+/// swapchain image could be acquired.
+#[derive(Debug, Default, Clone)]
+pub struct FrameStats {
+    frame_times: VecDeque<Duration>,
+    submitted: u64,
+    suboptimal: u64,
+    skipped: u64,
+}
+
+impl FrameStats {
+    /// Time taken by each of the last frames, oldest first
+    pub fn frame_times(&self) -> impl Iterator<Item = Duration> + '_ {
+        self.frame_times.iter().copied()
+    }
+
+    /// Mean time taken by the last frames, or zero before the first one
+    pub fn average(&self) -> Duration {
+        match self.frame_times.len() {
+            0 => Duration::default(),
+            n => self.frame_times.iter().sum::<Duration>() / n as u32,
+        }
+    }
+
+    /// Longest time taken by any of the last frames
+    pub fn max(&self) -> Duration {
+        self.frame_times().max().unwrap_or_default()
+    }
+
+    /// Frames drawn and queued for presentation since launch
+    pub fn submitted(&self) -> u64 {
+        self.submitted
+    }
+
+    /// Of the `submitted` frames, those whose swapchain image no longer matched the surface
+    /// exactly, which some platforms scale or present more slowly
+    pub fn suboptimal(&self) -> u64 {
+        self.suboptimal
+    }
+
+    /// Frames abandoned since launch because acquiring a swapchain image timed out or the
+    /// swapchain had to be rebuilt first
+    pub fn skipped(&self) -> u64 {
+        self.skipped
+    }
+
+    pub(crate) fn count_submitted(&mut self, suboptimal: bool) {
+        self.submitted += 1;
+        self.suboptimal += suboptimal as u64;
+    }
+
+    pub(crate) fn count_skipped(&mut self) {
+        self.skipped += 1;
+    }
+
+    pub(crate) fn push(&mut self, frame_time: Duration) {
+        if self.frame_times.len() == FRAME_HISTORY {
+            self.frame_times.pop_front();
+        }
+        self.frame_times.push_back(frame_time);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn memory_counts_every_sample() {
+        let mut stats = MemoryStats::default();
+        let size = PhysicalSize::new(100, 50);
+        stats.add_texture(size, wgpu::TextureFormat::Bgra8UnormSrgb, 1);
+        stats.add_texture(size, wgpu::TextureFormat::Rgba16Float, 4);
+        assert_eq!(stats.textures, 2);
+        assert_eq!(stats.texture_bytes, 100 * 50 * 4 + 100 * 50 * 4 * 8);
+    }
+
+    #[test]
+    fn frame_stats_start_empty() {
+        let stats = FrameStats::default();
+        assert_eq!(stats.average(), Duration::default());
+        assert_eq!(stats.max(), Duration::default());
+        assert_eq!(stats.frame_times().count(), 0);
+    }
+
+    #[test]
+    fn frame_stats_average_and_max() {
+        let mut stats = FrameStats::default();
+        for ms in &[10, 20, 30] {
+            stats.push(Duration::from_millis(*ms));
+        }
+        assert_eq!(stats.average(), Duration::from_millis(20));
+        assert_eq!(stats.max(), Duration::from_millis(30));
+    }
+
+    #[test]
+    fn frame_stats_keep_the_latest_history() {
+        let mut stats = FrameStats::default();
+        for ms in 0..FRAME_HISTORY as u64 + 5 {
+            stats.push(Duration::from_millis(ms));
+        }
+        assert_eq!(stats.frame_times().count(), FRAME_HISTORY);
+        assert_eq!(stats.frame_times().next(), Some(Duration::from_millis(5)));
+    }
+
+    #[test]
+    fn frame_stats_count_outcomes() {
+        let mut stats = FrameStats::default();
+        stats.count_submitted(false);
+        stats.count_submitted(true);
+        stats.count_skipped();
+        assert_eq!(stats.submitted(), 2);
+        assert_eq!(stats.suboptimal(), 1);
+        assert_eq!(stats.skipped(), 1);
+    }
+}
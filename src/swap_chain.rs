@@ -0,0 +1,174 @@
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use tracing::field::{Field, Visit};
+use tracing::subscriber::Interest;
+use tracing::{span, Dispatch, Event, Level, Metadata};
+
+/// Present mode wgpu substitutes when the surface does not support the requested one; every
+/// surface supports it
+const FALLBACK_PRESENT_MODE: wgpu::PresentMode = wgpu::PresentMode::Fifo;
+
+/// Swapchain formats which the surfaces of every backend wgpu 0.6 supports on desktop can present
+const PORTABLE_FORMATS: &[wgpu::TextureFormat] = &[
+    wgpu::TextureFormat::Bgra8UnormSrgb,
+    wgpu::TextureFormat::Bgra8Unorm,
+];
+
+/// Create a swapchain, returning it along with the present mode it actually uses. The requested
+/// present mode is tried first, then `Mailbox`, then `Fifo`.
+pub(crate) fn create_swap_chain(
+    device: &wgpu::Device,
+    surface: &wgpu::Surface,
+    desc: &wgpu::SwapChainDescriptor,
+) -> (wgpu::SwapChain, wgpu::PresentMode) {
+    let mut chain = vec![desc.present_mode];
+    for &mode in &[wgpu::PresentMode::Mailbox, FALLBACK_PRESENT_MODE] {
+        if !chain.contains(&mode) {
+            chain.push(mode);
+        }
+    }
+    for &present_mode in &chain[..chain.len() - 1] {
+        let desc = wgpu::SwapChainDescriptor {
+            present_mode,
+            ..*desc
+        };
+        let (swap_chain, fell_back) = try_create_swap_chain(device, surface, &desc);
+        if !fell_back {
+            return (swap_chain, present_mode);
+        }
+    }
+    let desc = wgpu::SwapChainDescriptor {
+        present_mode: FALLBACK_PRESENT_MODE,
+        ..*desc
+    };
+    (
+        device.create_swap_chain(surface, &desc),
+        FALLBACK_PRESENT_MODE,
+    )
+}
+
+/// Create a swapchain, returning whether wgpu fell back to `Fifo` because the surface doesn't
+/// support the requested present mode.
+///
+/// wgpu 0.6 does not expose surface capabilities, but it does report a present mode fallback
+/// while creating the swapchain. That report is watched for here, only for the duration of the
+/// call, with every event still passed on to the subscriber the application installed. This is
+/// a best-effort check: it recognizes the warning by its wording, so if a wgpu release rewords
+/// it, fallbacks go undetected and the requested present mode is reported as the one in use.
+fn try_create_swap_chain(
+    device: &wgpu::Device,
+    surface: &wgpu::Surface,
+    desc: &wgpu::SwapChainDescriptor,
+) -> (wgpu::SwapChain, bool) {
+    let detector = FallbackDetector {
+        fell_back: Arc::default(),
+        previous: tracing::dispatcher::get_default(Dispatch::clone),
+    };
+    let fell_back = detector.fell_back.clone();
+
+    let dispatch = Dispatch::new(detector);
+    let swap_chain =
+        tracing::dispatcher::with_default(&dispatch, || device.create_swap_chain(surface, desc));
+
+    let fell_back = fell_back.load(Ordering::Relaxed);
+    (swap_chain, fell_back)
+}
+
+/// Pick the first of `preferred` the surface is known to present, or `None` if there's none.
+///
+/// wgpu 0.6 can't list a surface's formats, and it panics on creating a swapchain of an
+/// unsupported one instead of returning an error, so only the formats in `PORTABLE_FORMATS`
+/// can be chosen safely; any others are skipped with a warning.
+pub(crate) fn select_format(preferred: &[wgpu::TextureFormat]) -> Option<wgpu::TextureFormat> {
+    let format = preferred
+        .iter()
+        .copied()
+        .find(|format| PORTABLE_FORMATS.contains(format));
+    for skipped in preferred
+        .iter()
+        .take_while(|&&skipped| Some(skipped) != format)
+    {
+        log::warn!(
+            "Swapchain format {:?} can't be checked for support, skipping it",
+            skipped
+        );
+    }
+    format
+}
+
+/// Subscriber which watches for wgpu-core's present mode fallback warning, forwarding everything
+/// to the dispatcher that was current before it
+struct FallbackDetector {
+    fell_back: Arc<AtomicBool>,
+    previous: Dispatch,
+}
+
+impl FallbackDetector {
+    fn is_watched(metadata: &Metadata<'_>) -> bool {
+        *metadata.level() == Level::WARN && metadata.target().starts_with("wgpu_core")
+    }
+}
+
+impl tracing::Subscriber for FallbackDetector {
+    fn register_callsite(&self, _metadata: &'static Metadata<'static>) -> Interest {
+        // Callsite interest is cached for every dispatcher, so only `enabled` may filter
+        Interest::sometimes()
+    }
+
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        Self::is_watched(metadata) || self.previous.enabled(metadata)
+    }
+
+    fn new_span(&self, span: &span::Attributes<'_>) -> span::Id {
+        self.previous.new_span(span)
+    }
+
+    fn record(&self, span: &span::Id, values: &span::Record<'_>) {
+        self.previous.record(span, values)
+    }
+
+    fn record_follows_from(&self, span: &span::Id, follows: &span::Id) {
+        self.previous.record_follows_from(span, follows)
+    }
+
+    fn event(&self, event: &Event<'_>) {
+        if Self::is_watched(event.metadata()) {
+            let mut visitor = MessageVisitor(String::new());
+            event.record(&mut visitor);
+            if visitor.0.contains("does not support present mode") {
+                self.fell_back.store(true, Ordering::Relaxed);
+            }
+        }
+        if self.previous.enabled(event.metadata()) {
+            self.previous.event(event);
+        }
+    }
+
+    fn enter(&self, span: &span::Id) {
+        self.previous.enter(span)
+    }
+
+    fn exit(&self, span: &span::Id) {
+        self.previous.exit(span)
+    }
+
+    fn clone_span(&self, id: &span::Id) -> span::Id {
+        self.previous.clone_span(id)
+    }
+
+    fn try_close(&self, id: span::Id) -> bool {
+        self.previous.try_close(id)
+    }
+}
+
+/// Collects the formatted `message` field of an event
+struct MessageVisitor(String);
+
+impl Visit for MessageVisitor {
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        if field.name() == "message" {
+            self.0 = format!("{:?}", value);
+        }
+    }
+}
@@ -0,0 +1,55 @@
+use winit::dpi::PhysicalSize;
+
+/// Create a 2D render target texture of the given size
+pub(crate) fn create_texture(
+    device: &wgpu::Device,
+    label: &str,
+    size: PhysicalSize<u32>,
+    sample_count: u32,
+    format: wgpu::TextureFormat,
+    usage: wgpu::TextureUsage,
+) -> wgpu::Texture {
+    device.create_texture(&wgpu::TextureDescriptor {
+        label: Some(label),
+        size: wgpu::Extent3d {
+            width: size.width,
+            height: size.height,
+            depth: 1,
+        },
+        mip_level_count: 1,
+        sample_count,
+        dimension: wgpu::TextureDimension::D2,
+        format,
+        usage,
+    })
+}
+
+/// Create a single-sampled 2D texture of the given size and return its view
+pub(crate) fn create_view(
+    device: &wgpu::Device,
+    label: &str,
+    size: PhysicalSize<u32>,
+    format: wgpu::TextureFormat,
+    usage: wgpu::TextureUsage,
+) -> wgpu::TextureView {
+    create_texture(device, label, size, 1, format, usage)
+        .create_view(&wgpu::TextureViewDescriptor::default())
+}
+
+/// Create the depth attachment for the managed pass
+pub(crate) fn create_depth_view(
+    device: &wgpu::Device,
+    label_prefix: &str,
+    size: PhysicalSize<u32>,
+    sample_count: u32,
+) -> wgpu::TextureView {
+    create_texture(
+        device,
+        &format!("{}Depth buffer", label_prefix),
+        size,
+        sample_count,
+        crate::DEPTH_FORMAT,
+        wgpu::TextureUsage::OUTPUT_ATTACHMENT,
+    )
+    .create_view(&wgpu::TextureViewDescriptor::default())
+}
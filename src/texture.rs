@@ -0,0 +1,101 @@
+use wgpu::TextureFormat;
+
+/// Linear and sRGB variants of formats which have both
+const SRGB_PAIRS: &[(TextureFormat, TextureFormat)] = &[
+    (TextureFormat::Rgba8Unorm, TextureFormat::Rgba8UnormSrgb),
+    (TextureFormat::Bgra8Unorm, TextureFormat::Bgra8UnormSrgb),
+    (TextureFormat::Bc1RgbaUnorm, TextureFormat::Bc1RgbaUnormSrgb),
+    (TextureFormat::Bc2RgbaUnorm, TextureFormat::Bc2RgbaUnormSrgb),
+    (TextureFormat::Bc3RgbaUnorm, TextureFormat::Bc3RgbaUnormSrgb),
+    (TextureFormat::Bc7RgbaUnorm, TextureFormat::Bc7RgbaUnormSrgb),
+];
+
+/// Find the (linear, sRGB) pair containing `format`, panicking if it has no counterpart
+fn srgb_pair(format: TextureFormat) -> (TextureFormat, TextureFormat) {
+    SRGB_PAIRS
+        .iter()
+        .copied()
+        .find(|&(linear, srgb)| format == linear || format == srgb)
+        .unwrap_or_else(|| panic!("{:?} has no sRGB/linear counterpart to view as", format))
+}
+
+/// Average size of one texel of the format, in bits; block-compressed formats pack 4x4 texels
+/// into 8 or 16 bytes
+pub(crate) fn bits_per_texel(format: TextureFormat) -> u32 {
+    use TextureFormat::*;
+    match format {
+        R8Unorm | R8Snorm | R8Uint | R8Sint => 8,
+        R16Uint | R16Sint | R16Float | Rg8Unorm | Rg8Snorm | Rg8Uint | Rg8Sint => 16,
+        R32Uint | R32Sint | R32Float | Rg16Uint | Rg16Sint | Rg16Float | Rgba8Unorm
+        | Rgba8UnormSrgb | Rgba8Snorm | Rgba8Uint | Rgba8Sint | Bgra8Unorm | Bgra8UnormSrgb
+        | Rgb10a2Unorm | Rg11b10Float | Depth32Float | Depth24Plus | Depth24PlusStencil8 => 32,
+        Rg32Uint | Rg32Sint | Rg32Float | Rgba16Uint | Rgba16Sint | Rgba16Float => 64,
+        Rgba32Uint | Rgba32Sint | Rgba32Float => 128,
+        Bc1RgbaUnorm | Bc1RgbaUnormSrgb | Bc4RUnorm | Bc4RSnorm => 4,
+        Bc2RgbaUnorm | Bc2RgbaUnormSrgb | Bc3RgbaUnorm | Bc3RgbaUnormSrgb | Bc5RgUnorm
+        | Bc5RgSnorm | Bc6hRgbUfloat | Bc6hRgbSfloat | Bc7RgbaUnorm | Bc7RgbaUnormSrgb => 8,
+    }
+}
+
+/// View a texture created with `format` through the linear (non-sRGB) variant of that format,
+/// so that sampling and storage see the raw encoded values.
+///
+/// Note that wgpu 0.6 does not create textures as format-mutable, so strict backends may reject
+/// views whose format differs from the texture's.
+pub fn linear_view(texture: &wgpu::Texture, format: TextureFormat) -> wgpu::TextureView {
+    let (linear, _) = srgb_pair(format);
+    reinterpreted_view(texture, linear)
+}
+
+/// View a texture created with `format` through the sRGB variant of that format, so that
+/// sampling decodes to linear color. See `linear_view` for caveats.
+pub fn srgb_view(texture: &wgpu::Texture, format: TextureFormat) -> wgpu::TextureView {
+    let (_, srgb) = srgb_pair(format);
+    reinterpreted_view(texture, srgb)
+}
+
+fn reinterpreted_view(texture: &wgpu::Texture, format: TextureFormat) -> wgpu::TextureView {
+    texture.create_view(&wgpu::TextureViewDescriptor {
+        format: Some(format),
+        ..Default::default()
+    })
+}
+
+/// Upload tightly packed rows of `bytes_per_pixel`-sized texels to the first mip level of
+/// `texture`. The queue repacks the rows into its own staging buffer at the 256-byte
+/// `COPY_BYTES_PER_ROW_ALIGNMENT`, so the data never needs padding by hand.
+pub fn write_texture_padded(
+    queue: &wgpu::Queue,
+    texture: &wgpu::Texture,
+    data: &[u8],
+    width: u32,
+    height: u32,
+    bytes_per_pixel: u32,
+) {
+    let bytes_per_row = width * bytes_per_pixel;
+    assert_eq!(
+        data.len(),
+        (bytes_per_row * height) as usize,
+        "Texture data must be {} rows of {} bytes",
+        height,
+        bytes_per_row
+    );
+    queue.write_texture(
+        wgpu::TextureCopyView {
+            texture,
+            mip_level: 0,
+            origin: wgpu::Origin3d::ZERO,
+        },
+        data,
+        wgpu::TextureDataLayout {
+            offset: 0,
+            bytes_per_row,
+            rows_per_image: height,
+        },
+        wgpu::Extent3d {
+            width,
+            height,
+            depth: 1,
+        },
+    );
+}
@@ -0,0 +1,71 @@
+/// Nice value the render thread is raised to with `LaunchConfig::raise_thread_priority`;
+/// raising it past 0 needs `CAP_SYS_NICE` or a suitable `RLIMIT_NICE`
+#[cfg(feature = "thread_priority")]
+const RAISED_NICE: i32 = -10;
+
+/// Setup of the thread running the event loop, from `LaunchConfig::thread_name` and
+/// `LaunchConfig::raise_thread_priority`
+pub(crate) fn configure_current(config: &crate::LaunchConfig) {
+    if let Some(name) = &config.thread_name {
+        set_current_name(name);
+    }
+    #[cfg(feature = "thread_priority")]
+    if config.raise_thread_priority {
+        raise_current_priority();
+    }
+}
+
+/// Name the calling thread, truncated to the 15 bytes Linux allows. On Linux this goes through
+/// `prctl`, which names the calling thread under any libc, glibc and musl alike.
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+fn set_current_name(name: &str) {
+    let mut end = name.len().min(15);
+    while !name.is_char_boundary(end) {
+        end -= 1;
+    }
+    let name = match std::ffi::CString::new(&name[..end]) {
+        Ok(name) => name,
+        Err(_) => {
+            log::warn!("Thread names can't contain NUL bytes");
+            return;
+        }
+    };
+    #[cfg(target_os = "linux")]
+    let result = match unsafe { libc::prctl(libc::PR_SET_NAME, name.as_ptr()) } {
+        0 => Ok(()),
+        _ => Err(std::io::Error::last_os_error()),
+    };
+    #[cfg(target_os = "macos")]
+    let result = match unsafe { libc::pthread_setname_np(name.as_ptr()) } {
+        0 => Ok(()),
+        err => Err(std::io::Error::from_raw_os_error(err)),
+    };
+    if let Err(err) = result {
+        log::warn!("Failed to name the render thread: {}", err);
+    }
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+fn set_current_name(_name: &str) {
+    log::warn!("Naming the render thread isn't supported on this platform");
+}
+
+/// Lower the calling thread's nice value, which Linux applies per thread
+#[cfg(all(feature = "thread_priority", target_os = "linux"))]
+fn raise_current_priority() {
+    let result = unsafe {
+        let tid = libc::syscall(libc::SYS_gettid) as libc::id_t;
+        libc::setpriority(libc::PRIO_PROCESS, tid, RAISED_NICE)
+    };
+    if result != 0 {
+        log::warn!(
+            "Failed to raise the render thread's priority: {}",
+            std::io::Error::last_os_error()
+        );
+    }
+}
+
+#[cfg(all(feature = "thread_priority", not(target_os = "linux")))]
+fn raise_current_priority() {
+    log::warn!("Raising the render thread's priority isn't supported on this platform");
+}
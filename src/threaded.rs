@@ -0,0 +1,194 @@
+use crate::{Context, DeviceEvent, DeviceId, Scene, WindowEvent};
+use std::sync::mpsc::{self, Receiver, Sender, SyncSender, TryRecvError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Update half of a `Threaded` scene, stepped continuously on a worker thread
+pub trait Simulation: Send + 'static {
+    /// State handed to the render thread after each step
+    type Snapshot: Send + 'static;
+    /// Messages sent from the render thread, such as player input
+    type Command: Send + 'static;
+
+    /// Advance the simulation by the time elapsed since the previous step
+    fn step(&mut self, dt: Duration) -> Self::Snapshot;
+
+    /// (Optional) act on a command; pending commands are delivered before each step
+    fn command(&mut self, _command: Self::Command) {}
+}
+
+/// Render half of a `Threaded` scene, run by `launch` on the main thread
+pub trait ThreadedScene: Scene {
+    type Simulation: Simulation;
+
+    /// Take the latest snapshot; called before `update` on frames where a new one is ready
+    fn receive(&mut self, ctx: &mut Context, snapshot: <Self::Simulation as Simulation>::Snapshot);
+
+    /// (Optional) send commands to the simulation; called every frame before `receive`
+    fn send_commands(&mut self, _commands: &Sender<<Self::Simulation as Simulation>::Command>) {}
+}
+
+/// Runs a scene's `Simulation` on its own thread, so that heavy updates don't hold up drawing.
+/// Launch as `launch::<Threaded<MyScene>>((simulation, args))`.
+///
+/// Snapshots are double-buffered: the simulation builds the next one while the previous waits
+/// to be received, and blocks once it is a full snapshot ahead of the render thread.
+pub struct Threaded<S: ThreadedScene> {
+    pub scene: S,
+    snapshots: Receiver<<S::Simulation as Simulation>::Snapshot>,
+    commands: Sender<<S::Simulation as Simulation>::Command>,
+}
+
+impl<S: ThreadedScene> Scene for Threaded<S> {
+    type Args = (S::Simulation, S::Args);
+
+    fn required_features() -> wgpu::Features {
+        S::required_features()
+    }
+
+    fn optional_features() -> wgpu::Features {
+        S::optional_features()
+    }
+
+    fn new(ctx: &mut Context, device: &wgpu::Device, (simulation, args): Self::Args) -> Self {
+        let (snapshot_tx, snapshots) = mpsc::sync_channel(1);
+        let (commands, command_rx) = mpsc::channel();
+        thread::Builder::new()
+            .name("wgpu_launchpad simulation".into())
+            .spawn(move || simulate(simulation, snapshot_tx, command_rx))
+            .expect("Spawn simulation thread");
+        Self {
+            scene: S::new(ctx, device, args),
+            snapshots,
+            commands,
+        }
+    }
+
+    fn update(&mut self, ctx: &mut Context) {
+        self.scene.send_commands(&self.commands);
+        match self.snapshots.try_recv() {
+            Ok(snapshot) => self.scene.receive(ctx, snapshot),
+            Err(TryRecvError::Empty) => {}
+            Err(TryRecvError::Disconnected) => panic!("Simulation thread panicked"),
+        }
+        self.scene.update(ctx);
+    }
+
+    fn compute(&mut self, encoder: &mut wgpu::CommandEncoder) {
+        self.scene.compute(encoder);
+    }
+
+    fn has_compute(&self) -> bool {
+        self.scene.has_compute()
+    }
+
+    fn frame_begin(&mut self, view: &wgpu::TextureView, frame_index: u64) {
+        self.scene.frame_begin(view, frame_index);
+    }
+
+    fn depth_prepass<'a>(&'a mut self, pass: &mut wgpu::RenderPass<'a>) {
+        self.scene.depth_prepass(pass);
+    }
+
+    fn draw_pass<'a>(&'a mut self, pass: &mut wgpu::RenderPass<'a>) {
+        self.scene.draw_pass(pass);
+    }
+
+    fn draw(&mut self, encoder: &mut wgpu::CommandEncoder, target: &wgpu::TextureView) {
+        self.scene.draw(encoder, target);
+    }
+
+    fn frame_end(&mut self) {
+        self.scene.frame_end();
+    }
+
+    fn is_ready(&self) -> bool {
+        self.scene.is_ready()
+    }
+
+    fn clear_color(&self) -> wgpu::Color {
+        self.scene.clear_color()
+    }
+
+    fn surface_usage(&self) -> wgpu::TextureUsage {
+        self.scene.surface_usage()
+    }
+
+    fn configure_surface(&self, desc: &mut wgpu::SwapChainDescriptor) {
+        self.scene.configure_surface(desc);
+    }
+
+    fn present(
+        &mut self,
+        encoder: &mut wgpu::CommandEncoder,
+        source: &wgpu::BindGroup,
+        target: &wgpu::TextureView,
+    ) -> bool {
+        self.scene.present(encoder, source, target)
+    }
+
+    fn recreate(
+        &mut self,
+        device: &wgpu::Device,
+        format: wgpu::TextureFormat,
+        width: u32,
+        height: u32,
+        sample_count: u32,
+    ) {
+        self.scene
+            .recreate(device, format, width, height, sample_count);
+    }
+
+    fn suspended(&mut self, ctx: &mut Context) {
+        self.scene.suspended(ctx);
+    }
+
+    fn resumed(&mut self, ctx: &mut Context) {
+        self.scene.resumed(ctx);
+    }
+
+    fn event(&mut self, ctx: &mut Context, event: &WindowEvent) {
+        self.scene.event(ctx, event);
+    }
+
+    fn raw_event(&mut self, event: &winit::event::Event<()>) {
+        self.scene.raw_event(event);
+    }
+
+    fn device_event(&mut self, ctx: &mut Context, device_id: DeviceId, event: &DeviceEvent) {
+        self.scene.device_event(ctx, device_id, event);
+    }
+
+    fn device_added(&mut self, ctx: &mut Context, device_id: DeviceId) {
+        self.scene.device_added(ctx, device_id);
+    }
+
+    fn device_removed(&mut self, ctx: &mut Context, device_id: DeviceId) {
+        self.scene.device_removed(ctx, device_id);
+    }
+}
+
+/// Step the simulation until the render thread goes away
+fn simulate<Sim: Simulation>(
+    mut simulation: Sim,
+    snapshots: SyncSender<Sim::Snapshot>,
+    commands: Receiver<Sim::Command>,
+) {
+    let mut last_step = Instant::now();
+    loop {
+        loop {
+            match commands.try_recv() {
+                Ok(command) => simulation.command(command),
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => return,
+            }
+        }
+
+        let now = Instant::now();
+        let snapshot = simulation.step(now - last_step);
+        last_step = now;
+        if snapshots.send(snapshot).is_err() {
+            return;
+        }
+    }
+}
@@ -0,0 +1,120 @@
+/// Builds a `VertexBufferDescriptor` from its attributes in order, computing their offsets,
+/// shader locations and the stride, so they can't drift out of step with each other
+#[derive(Debug, Clone)]
+pub struct VertexLayout {
+    step_mode: wgpu::InputStepMode,
+    attributes: Vec<wgpu::VertexAttributeDescriptor>,
+    next_location: wgpu::ShaderLocation,
+    stride: wgpu::BufferAddress,
+}
+
+impl Default for VertexLayout {
+    fn default() -> Self {
+        Self {
+            step_mode: wgpu::InputStepMode::Vertex,
+            attributes: Vec::new(),
+            next_location: 0,
+            stride: 0,
+        }
+    }
+}
+
+impl VertexLayout {
+    /// Layout of per-vertex data, with shader locations counting up from 0
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Layout of per-instance data, such as an `InstanceBuffer`, with shader locations counting
+    /// up from `first_location` so they can follow the vertex buffer's
+    pub fn instance(first_location: wgpu::ShaderLocation) -> Self {
+        Self {
+            step_mode: wgpu::InputStepMode::Instance,
+            next_location: first_location,
+            ..Self::default()
+        }
+    }
+
+    /// Append an attribute at the next shader location, right after the previous one
+    pub fn attr(mut self, format: wgpu::VertexFormat) -> Self {
+        self.attributes.push(wgpu::VertexAttributeDescriptor {
+            offset: self.stride,
+            format,
+            shader_location: self.next_location,
+        });
+        self.next_location += 1;
+        self.stride += format.size();
+        self
+    }
+
+    /// Skip `bytes` of data the shaders don't read
+    pub fn padding(mut self, bytes: wgpu::BufferAddress) -> Self {
+        self.stride += bytes;
+        self
+    }
+
+    /// Bytes per element, as the data must be laid out in the buffer
+    pub fn stride(&self) -> wgpu::BufferAddress {
+        self.stride
+    }
+
+    /// Descriptor for `PipelineConfig::vertex_buffers`
+    pub fn descriptor(&self) -> wgpu::VertexBufferDescriptor<'_> {
+        wgpu::VertexBufferDescriptor {
+            stride: self.stride,
+            step_mode: self.step_mode,
+            attributes: &self.attributes,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wgpu::VertexFormat;
+
+    #[test]
+    fn offsets_follow_each_attribute() {
+        let layout = VertexLayout::new()
+            .attr(VertexFormat::Float3)
+            .attr(VertexFormat::Uchar4Norm)
+            .padding(2)
+            .attr(VertexFormat::Short2)
+            .attr(VertexFormat::Float);
+        let descriptor = layout.descriptor();
+        let offsets: Vec<_> = descriptor.attributes.iter().map(|a| a.offset).collect();
+        let locations: Vec<_> = descriptor
+            .attributes
+            .iter()
+            .map(|a| a.shader_location)
+            .collect();
+        assert_eq!(offsets, [0, 12, 18, 22]);
+        assert_eq!(locations, [0, 1, 2, 3]);
+        assert_eq!(layout.stride(), 26);
+        assert_eq!(descriptor.stride, 26);
+        assert_eq!(descriptor.step_mode, wgpu::InputStepMode::Vertex);
+    }
+
+    #[test]
+    fn instance_locations_start_at_the_first() {
+        let layout = VertexLayout::instance(3)
+            .attr(VertexFormat::Float4)
+            .attr(VertexFormat::Half2);
+        let descriptor = layout.descriptor();
+        assert_eq!(descriptor.step_mode, wgpu::InputStepMode::Instance);
+        assert_eq!(descriptor.attributes[0].shader_location, 3);
+        assert_eq!(descriptor.attributes[1].shader_location, 4);
+        assert_eq!(descriptor.attributes[1].offset, 16);
+        assert_eq!(layout.stride(), 20);
+    }
+
+    #[test]
+    fn trailing_padding_only_grows_the_stride() {
+        let layout = VertexLayout::new()
+            .padding(4)
+            .attr(VertexFormat::Float2)
+            .padding(3);
+        assert_eq!(layout.descriptor().attributes[0].offset, 4);
+        assert_eq!(layout.stride(), 15);
+    }
+}
@@ -0,0 +1,57 @@
+use std::io::{self, Write};
+use std::path::Path;
+use std::process::{Child, ChildStdin, Command, Stdio};
+use winit::dpi::PhysicalSize;
+
+/// `ffmpeg` subprocess encoding the frames piped into it, for `LaunchConfig::record_video`
+pub(crate) struct VideoEncoder {
+    child: Child,
+    stdin: Option<ChildStdin>,
+}
+
+impl VideoEncoder {
+    /// Start encoding RGBA8 frames of the given size into `path`, in the container its
+    /// extension names
+    pub fn spawn(path: &Path, size: PhysicalSize<u32>, frame_rate: f64) -> io::Result<Self> {
+        let mut child = Command::new("ffmpeg")
+            .args(["-y", "-loglevel", "error"])
+            .args(["-f", "rawvideo", "-pix_fmt", "rgba"])
+            .arg("-video_size")
+            .arg(format!("{}x{}", size.width, size.height))
+            .arg("-framerate")
+            .arg(frame_rate.to_string())
+            .args(["-i", "-"])
+            // Most encoders need even dimensions for 4:2:0 chroma
+            .args([
+                "-vf",
+                "pad=ceil(iw/2)*2:ceil(ih/2)*2",
+                "-pix_fmt",
+                "yuv420p",
+            ])
+            .arg(path)
+            .stdin(Stdio::piped())
+            .spawn()?;
+        let stdin = child.stdin.take();
+        Ok(Self { child, stdin })
+    }
+
+    /// Append a frame of tightly packed RGBA8 rows, top row first
+    pub fn write(&mut self, pixels: &[u8]) -> io::Result<()> {
+        match self.stdin.as_mut() {
+            Some(stdin) => stdin.write_all(pixels),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Drop for VideoEncoder {
+    /// Close the pipe so `ffmpeg` finishes the file, and wait for it
+    fn drop(&mut self) {
+        drop(self.stdin.take());
+        match self.child.wait() {
+            Ok(status) if !status.success() => log::error!("ffmpeg exited with {}", status),
+            Ok(_) => {}
+            Err(err) => log::error!("Failed to wait for ffmpeg: {}", err),
+        }
+    }
+}
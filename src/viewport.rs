@@ -0,0 +1,78 @@
+use winit::dpi::PhysicalSize;
+
+/// Rectangle of a render target to draw into, in pixels from its top left corner
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Viewport {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+}
+
+impl Viewport {
+    /// The largest rectangle of the given aspect ratio (width / height) that fits in a target of
+    /// `size`, centered so that the rest of the target forms even bars on either side
+    pub fn letterbox(size: PhysicalSize<u32>, aspect: f32) -> Self {
+        let (target_width, target_height) = (size.width as f32, size.height as f32);
+        let (width, height) = if target_width > target_height * aspect {
+            (target_height * aspect, target_height)
+        } else {
+            (target_width, target_width / aspect)
+        };
+        Self {
+            x: ((target_width - width) / 2.0).floor(),
+            y: ((target_height - height) / 2.0).floor(),
+            width,
+            height,
+        }
+    }
+
+    /// Restrict the pass's drawing to this rectangle, over the full depth range
+    pub fn apply(&self, pass: &mut wgpu::RenderPass) {
+        pass.set_viewport(self.x, self.y, self.width, self.height, 0.0, 1.0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn letterbox_bars_on_the_sides_of_a_wide_target() {
+        let viewport = Viewport::letterbox(PhysicalSize::new(200, 100), 1.0);
+        assert_eq!(
+            viewport,
+            Viewport {
+                x: 50.0,
+                y: 0.0,
+                width: 100.0,
+                height: 100.0,
+            }
+        );
+    }
+
+    #[test]
+    fn letterbox_bars_above_and_below_a_tall_target() {
+        let viewport = Viewport::letterbox(PhysicalSize::new(160, 200), 16.0 / 9.0);
+        assert_eq!(viewport.x, 0.0);
+        assert_eq!(viewport.width, 160.0);
+        assert_eq!(viewport.height, 90.0);
+        assert_eq!(viewport.y, 55.0);
+    }
+
+    #[test]
+    fn letterbox_offsets_round_down() {
+        let viewport = Viewport::letterbox(PhysicalSize::new(101, 100), 1.0);
+        assert_eq!(viewport.x, 0.0);
+        assert_eq!(viewport.width, 100.0);
+    }
+
+    #[test]
+    fn letterbox_fills_a_matching_target() {
+        let viewport = Viewport::letterbox(PhysicalSize::new(1920, 1080), 16.0 / 9.0);
+        assert_eq!(viewport.x, 0.0);
+        assert_eq!(viewport.y, 0.0);
+        assert_eq!(viewport.width, 1920.0);
+        assert_eq!(viewport.height, 1080.0);
+    }
+}
@@ -0,0 +1,67 @@
+use crate::error::{report, ErrorHandler, LaunchError};
+use std::sync::{Arc, Mutex, Weak};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Frame currently being waited on
+struct InFlight {
+    frame_index: u64,
+    started: Instant,
+    reported: bool,
+}
+
+/// Reports frames which take longer than a timeout to finish, from a background thread
+pub(crate) struct Watchdog {
+    in_flight: Arc<Mutex<Option<InFlight>>>,
+}
+
+impl Watchdog {
+    pub fn new(timeout: Duration, handler: Option<ErrorHandler>) -> Self {
+        let in_flight = Arc::new(Mutex::new(None));
+        let weak = Arc::downgrade(&in_flight);
+        thread::Builder::new()
+            .name("wgpu_launchpad watchdog".into())
+            .spawn(move || watch(weak, timeout, handler))
+            .expect("Spawn watchdog thread");
+        Self { in_flight }
+    }
+
+    /// Start timing a frame
+    pub fn begin(&self, frame_index: u64) {
+        *self.in_flight.lock().unwrap() = Some(InFlight {
+            frame_index,
+            started: Instant::now(),
+            reported: false,
+        });
+    }
+
+    /// Stop timing the current frame
+    pub fn end(&self) {
+        *self.in_flight.lock().unwrap() = None;
+    }
+}
+
+/// Check on the in-flight frame until the watchdog is dropped
+fn watch(
+    in_flight: Weak<Mutex<Option<InFlight>>>,
+    timeout: Duration,
+    handler: Option<ErrorHandler>,
+) {
+    while let Some(in_flight) = in_flight.upgrade() {
+        if let Some(frame) = in_flight.lock().unwrap().as_mut() {
+            let elapsed = frame.started.elapsed();
+            if elapsed > timeout && !frame.reported {
+                frame.reported = true;
+                report(
+                    &handler,
+                    LaunchError::FrameTimeout {
+                        frame_index: frame.frame_index,
+                        elapsed,
+                    },
+                );
+            }
+        }
+        drop(in_flight);
+        thread::sleep(timeout / 4);
+    }
+}